@@ -0,0 +1,5 @@
+pub mod body;
+pub mod calls;
+pub mod check;
+pub mod coverage;
+pub mod search;
\ No newline at end of file
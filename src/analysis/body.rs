@@ -0,0 +1,110 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_yaml::Value;
+
+use crate::cache::scripts::ScriptContents;
+use crate::structs::doc::Doc;
+
+/// Top-level keys that configure a script rather than hold its body.
+const META_KEYS: [&str; 3] = ["type", "debug", "definitions"];
+
+/// Returns every line of text in a value, including the keys of nested maps such as `if` blocks.
+pub fn lines(value: &Value) -> Vec<&str> {
+    match value {
+        Value::String(s) => vec![s.as_str()],
+        Value::Sequence(seq) => seq.iter().flat_map(lines).collect(),
+        Value::Mapping(map) => map.iter()
+            .flat_map(|(k, v)| lines(k).into_iter().chain(lines(v)))
+            .collect(),
+        _ => Vec::new()
+    }
+}
+
+/// Returns every line of a script's body, leaving out its kDoc and configuration keys.
+pub fn body_lines(contents: &ScriptContents) -> Vec<&str> {
+    let doc = Doc::source_key(contents);
+
+    contents.iter()
+        .filter(|(k, _)| Some(k.as_str()) != doc && !META_KEYS.contains(&k.as_str()))
+        .flat_map(|(_, v)| lines(v))
+        .collect()
+}
+
+/// Returns the names of other scripts mentioned in a script's body, such as in `run` commands or `proc` tags.
+pub fn references(contents: &ScriptContents, names: &BTreeSet<String>) -> BTreeSet<String> {
+    body_lines(contents).iter()
+        .flat_map(|line| line.split(|c: char| !(c.is_alphanumeric() || c == '_')))
+        .filter(|word| names.contains(*word))
+        .map(String::from)
+        .collect()
+}
+
+/// Returns the name a `define` or `as:` argument sets, without any map key or action.
+fn def_name(arg: &str) -> Option<String> {
+    let name = arg.split([':', '.']).next()?;
+
+    if name.is_empty() || name.contains('<') {
+        None
+    } else {
+        Some(name.to_lowercase())
+    }
+}
+
+/// Infers the definitions a script sets, mapped to where each one comes from.
+///
+/// This covers the `definitions` key, `define` and `definemap` commands, and the loop
+/// definitions of `foreach`, `repeat` and `while`.
+pub fn defined(contents: &ScriptContents) -> BTreeMap<String, &'static str> {
+    let mut result = BTreeMap::new();
+
+    for name in super::check::definitions(contents).unwrap_or_default() {
+        result.insert(name.to_lowercase(), "definitions key");
+    }
+
+    for line in body_lines(contents) {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default().trim_start_matches('~').to_lowercase();
+        let args = words.collect::<Vec<&str>>();
+
+        let explicit = args.iter()
+            .find_map(|a| a.strip_prefix("as:"))
+            .and_then(def_name);
+
+        let (names, source): (Vec<Option<String>>, &'static str) = match command.as_str() {
+            "define" | "definemap" => (vec![args.first().and_then(|a| def_name(a.trim_end_matches(':')))], "define"),
+            "foreach" => (vec![
+                explicit.or_else(|| Some("value".to_owned())),
+                args.iter().find_map(|a| a.strip_prefix("key:")).and_then(def_name),
+                Some("loop_index".to_owned())
+            ], "foreach"),
+            "repeat" => (vec![explicit.or_else(|| Some("value".to_owned()))], "repeat"),
+            "while" => (vec![Some("loop_index".to_owned())], "while"),
+            _ => continue
+        };
+
+        for name in names.into_iter().flatten() {
+            result.entry(name).or_insert(source);
+        }
+    }
+
+    result
+}
+
+/// Returns the definitions a script's body reads with `<[name]>` tags.
+pub fn used(contents: &ScriptContents) -> BTreeSet<String> {
+    let mut result = BTreeSet::new();
+
+    for line in body_lines(contents) {
+        let mut rest = line;
+
+        while let Some(i) = rest.find("<[") {
+            rest = &rest[i + 2..];
+
+            if let Some(name) = rest.split(']').next().and_then(def_name) {
+                result.insert(name);
+            }
+        }
+    }
+
+    result
+}
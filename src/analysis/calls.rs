@@ -0,0 +1,89 @@
+use super::body::body_lines;
+use crate::cache::scripts::ScriptContents;
+
+/// A `<proc[name].context[...]>` tag found in a script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcCall {
+    pub tag: String,
+    pub name: String,
+    pub args: Vec<String>
+}
+
+/// Reads up to the bracket closing the one just before `text`, returning its contents and the rest.
+fn bracketed(text: &str) -> Option<(&str, &str)> {
+    let mut depth = 0;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return Some((&text[..i], &text[i + 1..])),
+            ']' => depth -= 1,
+            _ => ()
+        }
+    }
+
+    None
+}
+
+/// Splits a `context` list on pipes that aren't inside a tag.
+fn split_args(text: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+
+    for c in text.chars() {
+        match c {
+            '<' | '[' => depth += 1,
+            '>' | ']' => depth -= 1,
+            '|' if depth == 0 => {
+                args.push(current.clone());
+                current.clear();
+                continue;
+            }
+            _ => ()
+        }
+
+        current.push(c);
+    }
+
+    args.push(current);
+    args
+}
+
+/// Finds every procedure call with a literal name and `context` in a line of script.
+pub fn proc_calls(line: &str) -> Vec<ProcCall> {
+    let mut result = Vec::new();
+    let mut rest = line;
+
+    while let Some(i) = rest.find("<proc[") {
+        let start = &rest[i..];
+        rest = &start[1..];
+
+        let (name, after) = match bracketed(&start[6..]) {
+            Some(pair) => pair,
+            None => continue
+        };
+
+        let args = match after.strip_prefix(".context[").and_then(bracketed) {
+            Some((args, _)) => args,
+            None => continue
+        };
+
+        if name.contains('<') {
+            continue;
+        }
+
+        result.push(ProcCall {
+            tag: format!("<proc[{}].context[{}]>", name, args),
+            name: name.to_owned(),
+            args: split_args(args)
+        });
+    }
+
+    result
+}
+
+/// Finds every procedure call in a script's body.
+pub fn find_calls(contents: &ScriptContents) -> Vec<ProcCall> {
+    body_lines(contents).into_iter().flat_map(proc_calls).collect()
+}
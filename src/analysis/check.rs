@@ -0,0 +1,354 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use semver::Version;
+use serde_yaml::Value;
+
+use super::calls::find_calls;
+use crate::cache::scripts::{ScriptContents, ScriptFile};
+use crate::structs::doc::{Deprecation, Doc, DocArg, Example};
+use crate::structs::tag_type::TagType;
+
+/// A problem found in a script's kDoc.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub script: String,
+    pub path: String,
+    pub message: String,
+    /// Whether the problem might be intended, such as a suspicious procedure call.
+    pub warning: bool
+}
+
+pub fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "nothing",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "text",
+        Value::Sequence(_) => "a list",
+        Value::Mapping(_) => "a map"
+    }
+}
+
+fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        _ => format!("<{}>", type_name(key))
+    }
+}
+
+/// Returns the names in a script's `definitions` key, if it has one.
+pub fn definitions(contents: &ScriptContents) -> Option<Vec<String>> {
+    let names = match contents.get("definitions")? {
+        Value::String(s) => s.split('|').map(String::from).collect(),
+        Value::Sequence(seq) => seq.iter().map(key_name).collect(),
+        other => vec![key_name(other)]
+    };
+
+    Some(names.iter()
+        // Definitions may have a default value or description in brackets
+        .map(|d| d.split('[').next().unwrap_or_default().trim().to_owned())
+        .filter(|d| !d.is_empty())
+        .collect())
+}
+
+/// Returns the keys of a data script that hold actual data.
+pub fn data_keys(contents: &ScriptContents) -> Vec<String> {
+    let doc = Doc::source_key(contents);
+
+    contents.keys()
+        .filter(|k| !["type", "debug"].contains(&k.as_str()) && Some(k.as_str()) != doc)
+        .cloned()
+        .collect()
+}
+
+fn expect(value: &Value, at: &str, expected: &str, valid: bool, issues: &mut Vec<String>) {
+    if !valid {
+        issues.push(format!("'{}' should be {}, found {}.", at, expected, type_name(value)));
+    }
+}
+
+fn check_arg(value: &Value, at: &str, issues: &mut Vec<String>) {
+    let map = match value {
+        Value::Mapping(map) => map,
+        _ => {
+            expect(value, at, "a map with 'description' and 'type' keys", false, issues);
+            return;
+        }
+    };
+
+    for (k, v) in map {
+        let key = key_name(k);
+
+        match Doc::canonical_key(&DocArg::KEYS, &key) {
+            Some(name) => {
+                let at = format!("{}.{}", at, name);
+                expect(v, &at, "text", v.is_string(), issues);
+
+                if let (Some(t), "type") = (v.as_str(), name) {
+                    if let Err(e) = t.parse::<TagType>() {
+                        issues.push(format!("'{}' is not a valid type: {}.", at, e));
+                    }
+                }
+            }
+            None => issues.push(format!("Unknown key '{}' in '{}'.", key, at))
+        }
+    }
+}
+
+fn check_args(value: &Value, at: &str, issues: &mut Vec<String>) -> Vec<String> {
+    let map = match value {
+        Value::Mapping(map) => map,
+        _ => {
+            expect(value, at, "a map of names to arguments", false, issues);
+            return Vec::new();
+        }
+    };
+
+    map.iter()
+        .map(|(k, v)| {
+            let name = key_name(k);
+            check_arg(v, &format!("{}.{}", at, name), issues);
+            name
+        })
+        .collect()
+}
+
+fn check_script_names(value: &Value, at: &str, scripts: &BTreeSet<String>, issues: &mut Vec<String>) {
+    let list = match value {
+        Value::Sequence(seq) => seq,
+        _ => return expect(value, at, "a list of script names", false, issues)
+    };
+
+    for item in list {
+        match item.as_str() {
+            Some(s) if !scripts.contains(s) => issues.push(format!("'{}' names the script '{}', which does not exist.", at, s)),
+            Some(_) => (),
+            None => expect(item, at, "a list of script names", false, issues)
+        }
+    }
+}
+
+fn check_deprecated(value: &Value, scripts: &BTreeSet<String>, issues: &mut Vec<String>) {
+    let map = match value {
+        Value::Bool(_) => return,
+        Value::Mapping(map) => map,
+        _ => return expect(value, "deprecated", "true, false or a map with 'reason' and 'replacement' keys", false, issues)
+    };
+
+    for (k, v) in map {
+        let key = key_name(k);
+
+        match Doc::canonical_key(&Deprecation::KEYS, &key) {
+            Some(name) => {
+                let at = format!("deprecated.{}", name);
+                expect(v, &at, "text", v.is_string(), issues);
+
+                match v.as_str() {
+                    Some(s) if name == "replacement" && !scripts.contains(s) => issues.push(format!("'{}' names the script '{}', which does not exist.", at, s)),
+                    _ => ()
+                }
+            }
+            None => issues.push(format!("Unknown key '{}' in 'deprecated'.", key))
+        }
+    }
+}
+
+fn check_examples(value: &Value, issues: &mut Vec<String>) {
+    let list = match value {
+        Value::Sequence(seq) => seq,
+        _ => return expect(value, "examples", "a list of examples", false, issues)
+    };
+
+    for (i, item) in list.iter().enumerate() {
+        let at = format!("examples.{}", i + 1);
+
+        let map = match item {
+            Value::String(_) => continue,
+            Value::Mapping(map) => map,
+            _ => {
+                expect(item, &at, "text or a map with 'caption' and 'code' keys", false, issues);
+                continue;
+            }
+        };
+
+        let mut has_code = false;
+
+        for (k, v) in map {
+            let key = key_name(k);
+
+            match Doc::canonical_key(&Example::KEYS, &key) {
+                Some(name) => {
+                    has_code |= name == "code";
+                    expect(v, &format!("{}.{}", at, name), "text", v.is_string(), issues);
+                }
+                None => issues.push(format!("Unknown key '{}' in '{}'.", key, at))
+            }
+        }
+
+        if !has_code {
+            issues.push(format!("'{}' is missing its 'code'.", at));
+        }
+    }
+}
+
+fn check_since(value: &Value, issues: &mut Vec<String>) {
+    let version = match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => return expect(value, "since", "a version", false, issues)
+    };
+
+    if Doc::coerce_version(value).is_none() {
+        let reason = Version::parse(&version).err().map(|e| format!(": {}", e)).unwrap_or_default();
+        issues.push(format!("'since' is not a valid version{}. It's left out of the documentation.", reason));
+    }
+}
+
+fn compare(documented: &[String], actual: &[String], key: &str, noun: &str, issues: &mut Vec<String>) {
+    for d in documented.iter().filter(|d| !actual.contains(d)) {
+        issues.push(format!("'{}' documents '{}', which is not a {} of the script.", key, d, noun));
+    }
+
+    for a in actual.iter().filter(|a| !documented.contains(a)) {
+        issues.push(format!("The {} '{}' is not documented in '{}'.", noun, a, key));
+    }
+}
+
+/// Validates a single script's kDoc, returning a message for each problem.
+pub fn check_doc(contents: &ScriptContents, scripts: &BTreeSet<String>) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    let source = match Doc::source_key(contents) {
+        Some(key) => key,
+        None => return issues
+    };
+
+    let map = match contents.get(source) {
+        Some(Value::Mapping(map)) => map,
+        Some(other) => {
+            expect(other, source, "a map of kDoc keys", false, &mut issues);
+            return issues;
+        }
+        None => return issues
+    };
+
+    let script_type = contents.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+
+    for (k, v) in map {
+        let key = key_name(k);
+
+        let name = match Doc::canonical_key(&Doc::KEYS, &key) {
+            Some(name) => name,
+            None => {
+                issues.push(format!("Unknown kDoc key '{}'.", key));
+                continue;
+            }
+        };
+
+        match name {
+            "deprecated" => check_deprecated(v, scripts, &mut issues),
+            "private" => expect(v, name, "true or false", v.is_bool(), &mut issues),
+            "description" | "usage" | "author" => expect(v, name, "text", v.is_string(), &mut issues),
+            "examples" => check_examples(v, &mut issues),
+            "since" => check_since(v, &mut issues),
+            "tags" => {
+                let valid = v.as_sequence().is_some_and(|seq| seq.iter().all(Value::is_string));
+                expect(v, name, "a list of text", valid, &mut issues);
+            }
+            "determine" => check_arg(v, name, &mut issues),
+            "uses" | "see" => check_script_names(v, name, scripts, &mut issues),
+            "defs" => {
+                let documented = check_args(v, name, &mut issues);
+
+                match definitions(contents) {
+                    Some(actual) => compare(&documented, &actual, name, "definition", &mut issues),
+                    None => issues.push("'defs' is set, but the script has no 'definitions' key.".to_owned())
+                }
+            }
+            "keys" => {
+                let documented = check_args(v, name, &mut issues);
+
+                if script_type == "data" {
+                    compare(&documented, &data_keys(contents), name, "key", &mut issues);
+                } else {
+                    issues.push("'keys' only applies to data scripts.".to_owned());
+                }
+            }
+            _ => ()
+        }
+    }
+
+    issues
+}
+
+/// Returns each procedure's definitions in order, along with their documented types.
+fn procedures(files: &[(String, ScriptFile)]) -> BTreeMap<String, Vec<(String, Option<TagType>)>> {
+    let mut result = BTreeMap::new();
+
+    for (name, contents) in files.iter().flat_map(|(_, file)| file) {
+        if contents.get("type").and_then(|t| t.as_str()) != Some("procedure") {
+            continue;
+        }
+
+        let defs = Doc::from(contents).and_then(|d| d.defs).unwrap_or_default();
+        let args = definitions(contents).unwrap_or_default().into_iter()
+            .map(|d| {
+                let tag_type = defs.get(&d).and_then(DocArg::tag_type).and_then(Result::ok);
+                (d, tag_type)
+            })
+            .collect();
+
+        result.insert(name.clone(), args);
+    }
+
+    result
+}
+
+/// Returns a warning for each literal passed to a procedure that doesn't match the documented type.
+pub fn check_calls(contents: &ScriptContents, procs: &BTreeMap<String, Vec<(String, Option<TagType>)>>) -> Vec<String> {
+    find_calls(contents).into_iter()
+        .flat_map(|call| {
+            let defs = procs.get(&call.name).cloned().unwrap_or_default();
+
+            call.args.iter()
+                .zip(defs)
+                .filter_map(|(arg, (def, tag_type))| match tag_type {
+                    Some(t) if !t.accepts(arg) => Some(format!(
+                        "'{}' passes '{}' as '{}', which should be {}.", call.tag, arg.trim(), def, t
+                    )),
+                    _ => None
+                })
+                .collect::<Vec<String>>()
+        })
+        .collect()
+}
+
+/// Validates the kDoc of every script, and warns about procedure calls that don't match it.
+pub fn check(files: &[(String, ScriptFile)]) -> Vec<Issue> {
+    let scripts = files.iter()
+        .flat_map(|(_, file)| file.keys().cloned())
+        .collect::<BTreeSet<String>>();
+
+    let procs = procedures(files);
+    let mut result = Vec::new();
+
+    for (path, file) in files {
+        for (name, contents) in file {
+            let errors = check_doc(contents, &scripts).into_iter().map(|m| (m, false));
+            let warnings = check_calls(contents, &procs).into_iter().map(|m| (m, true));
+
+            for (message, warning) in errors.chain(warnings) {
+                result.push(Issue {
+                    script: name.clone(),
+                    path: path.clone(),
+                    message,
+                    warning
+                });
+            }
+        }
+    }
+
+    result
+}
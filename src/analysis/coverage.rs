@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use super::check::definitions;
+use crate::cache::scripts::{ScriptContents, ScriptFile};
+use crate::structs::doc::Doc;
+
+/// Documentation counts for a group of scripts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub scripts: usize,
+    pub documented: usize,
+    pub fields: usize,
+    pub present: usize
+}
+
+impl Tally {
+    fn add(&mut self, script: &ScriptCoverage) {
+        self.scripts += 1;
+        self.documented += script.documented as usize;
+        self.fields += script.required.len();
+        self.present += script.required.len() - script.missing.len();
+    }
+
+    /// The percentage of applicable kDoc fields that are present.
+    pub fn percent(&self) -> f64 {
+        if self.fields == 0 {
+            100.0
+        } else {
+            self.present as f64 * 100.0 / self.fields as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScriptCoverage {
+    pub name: String,
+    pub path: String,
+    pub script_type: String,
+    pub documented: bool,
+    pub required: Vec<&'static str>,
+    pub missing: Vec<&'static str>
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    pub scripts: Vec<ScriptCoverage>,
+    pub by_file: BTreeMap<String, Tally>,
+    pub by_type: BTreeMap<String, Tally>,
+    pub total: Tally
+}
+
+/// Returns the kDoc fields a script should document.
+pub fn required_fields(script_type: &str, contents: &ScriptContents) -> Vec<&'static str> {
+    let mut fields = vec!["description"];
+
+    if script_type == "task" || script_type == "procedure" {
+        fields.push("usage");
+    }
+
+    if script_type == "procedure" {
+        fields.push("determine");
+    }
+
+    if definitions(contents).is_some_and(|d| !d.is_empty()) {
+        fields.push("defs");
+    }
+
+    fields
+}
+
+fn has_field(doc: &Doc, field: &str) -> bool {
+    match field {
+        "description" => doc.description.is_some(),
+        "usage" => doc.usage.is_some(),
+        "determine" => doc.determine.is_some(),
+        "defs" => doc.defs.is_some(),
+        _ => false
+    }
+}
+
+/// Computes kDoc coverage for every script.
+pub fn coverage(files: &[(String, ScriptFile)]) -> Coverage {
+    let mut result = Coverage::default();
+
+    for (path, file) in files {
+        for (name, contents) in file {
+            let script_type = match contents.get("type").and_then(|t| t.as_str()) {
+                Some(t) => t.to_owned(),
+                None => continue
+            };
+
+            let doc = Doc::from(contents);
+            let required = required_fields(&script_type, contents);
+            let missing = required.iter()
+                .copied()
+                .filter(|f| !doc.as_ref().is_some_and(|d| has_field(d, f)))
+                .collect();
+
+            let script = ScriptCoverage {
+                name: name.clone(),
+                path: path.clone(),
+                script_type: script_type.clone(),
+                documented: doc.is_some(),
+                required,
+                missing
+            };
+
+            result.by_file.entry(path.clone()).or_default().add(&script);
+            result.by_type.entry(script_type).or_default().add(&script);
+            result.total.add(&script);
+            result.scripts.push(script);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,142 @@
+use std::cmp::Reverse;
+use std::path::Path;
+
+use crate::cache::scripts::Scripts;
+use crate::structs::script::Script;
+
+/// A script matching a search query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub name: String,
+    pub score: u32,
+    /// The fields the query matched, such as `name` or `events`.
+    pub fields: Vec<&'static str>
+}
+
+/// Narrows a search down to certain scripts.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub script_type: Option<String>,
+    pub path: Option<String>
+}
+
+impl Filter {
+    fn allows(&self, script: &Script) -> bool {
+        let type_matches = self.script_type.as_ref()
+            .is_none_or(|t| script.script_type.eq_ignore_ascii_case(t));
+
+        let path_matches = self.path.as_ref()
+            .is_none_or(|p| Path::new(script.path.trim_start_matches("./")).starts_with(p.trim_start_matches("./")));
+
+        type_matches && path_matches
+    }
+}
+
+/// Scores how well a single lowercase term matches some text, or `None` if it doesn't.
+///
+/// Exact matches rank above prefixes, prefixes above substrings, and substrings above
+/// fuzzy matches where the term's characters appear in order, e.g. `spwn` in `spawn_npc`.
+fn score(term: &str, text: &str, fuzzy: bool) -> Option<u32> {
+    let text = text.to_lowercase();
+
+    if text == term {
+        return Some(100);
+    }
+
+    if text.starts_with(term) {
+        return Some(80);
+    }
+
+    if let Some(i) = text.find(term) {
+        let word_start = text[..i].ends_with(|c: char| !c.is_alphanumeric());
+        return Some(if word_start { 70 } else { 60 });
+    }
+
+    if !fuzzy {
+        return None;
+    }
+
+    let mut chars = text.chars().enumerate();
+    let mut last = None;
+    let mut gaps = 0;
+
+    for t in term.chars() {
+        let (i, _) = chars.find(|(_, c)| *c == t)?;
+
+        if last.is_some_and(|l| i != l + 1) {
+            gaps += 1;
+        }
+
+        last = Some(i);
+    }
+
+    Some(40u32.saturating_sub(gaps * 5).max(1))
+}
+
+/// Returns the searchable fields of a script, along with the weight of a match in each.
+///
+/// Only names are matched fuzzily, since almost any short term appears in order somewhere in a long description.
+fn fields<'a>(name: &'a str, script: &'a Script) -> Vec<(&'static str, u32, bool, Vec<&'a str>)> {
+    let doc = script.doc.as_ref();
+    let command = script.command.as_ref();
+
+    vec![
+        ("name", 3, true, vec![name]),
+        ("command", 3, true, command.map(|c| {
+            std::iter::once(c.name.as_str()).chain(c.aliases.iter().map(String::as_str)).collect()
+        }).unwrap_or_default()),
+        ("events", 2, false, script.events.iter().flatten().map(String::as_str).collect()),
+        ("description", 1, false, doc.and_then(|d| d.description.as_deref()).into_iter().collect()),
+        ("usage", 1, false, doc.and_then(|d| d.usage.as_deref()).into_iter().collect())
+    ]
+}
+
+fn search_script(terms: &[String], name: &str, script: &Script) -> Option<SearchResult> {
+    let fields = fields(name, script);
+    let mut total = 0;
+    let mut matched = Vec::new();
+
+    // Every term has to match at least one field
+    for term in terms {
+        let (field, best) = fields.iter()
+            .filter_map(|(field, weight, fuzzy, texts)| {
+                texts.iter()
+                    .filter_map(|t| score(term, t, *fuzzy))
+                    .max()
+                    .map(|s| (*field, s * weight))
+            })
+            .max_by_key(|(_, s)| *s)?;
+
+        total += best;
+
+        if !matched.contains(&field) {
+            matched.push(field);
+        }
+    }
+
+    Some(SearchResult {
+        name: name.to_owned(),
+        score: total,
+        fields: matched
+    })
+}
+
+/// Searches script names, descriptions, usage, event lines and command names, best matches first.
+pub fn search(scripts: &Scripts, query: &str, filter: &Filter) -> Vec<SearchResult> {
+    let terms = query.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<String>>();
+
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results = scripts.iter()
+        .filter(|(_, script)| filter.allows(script))
+        .filter_map(|(name, script)| search_script(&terms, name, script))
+        .collect::<Vec<SearchResult>>();
+
+    // Ties are kept in name order, since the sort is stable
+    results.sort_by_key(|r| Reverse(r.score));
+    results
+}
@@ -1,31 +1,68 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::{read_to_string, write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde_yaml::{from_str, Value, to_string};
 use walkdir::WalkDir;
 
-use crate::io::log::err;
+use crate::io::fs::get_project;
+use crate::structs::project::Project;
 use crate::structs::script::Script;
 
 pub type ScriptContents = BTreeMap<String, Value>;
 pub type ScriptFile = BTreeMap<String, ScriptContents>;
-
-pub fn find_scripts_raw() -> Vec<(String, ScriptFile)> {
-    // Get files in current directory
-    let files = WalkDir::new(".").into_iter().filter_map(|e| e.ok());
+pub type Scripts = BTreeMap<String, Script>;
+
+/// Returns the paths of every `.dsc` file under `root`, or under the project's sources if it sets any.
+pub fn script_files(root: &str) -> Vec<PathBuf> {
+    let roots = match get_project(Some(root)).ok().and_then(|p| p.sources).filter(|s| !s.is_empty()) {
+        Some(sources) => sources.iter().map(|s| Path::new(root).join(s)).collect(),
+        None => vec![PathBuf::from(root)]
+    };
+    // Get files in the root directories
+    let files = roots.into_iter().flat_map(|r| WalkDir::new(r).into_iter().filter_map(|e| e.ok()));
     // File extension needs to be .dsc
-    let script_files = files.filter(|f| f.path().extension().unwrap_or_default() == "dsc");
+    files.filter(|f| f.path().extension().unwrap_or_default() == "dsc")
+        .map(|f| f.into_path())
+        .collect()
+}
+
+/// Suggests source directories for the scripts under `root`: the top-level directories holding `.dsc` files.
+///
+/// Returns `.` if there are scripts directly in `root`.
+pub fn script_roots(root: &str) -> Vec<String> {
+    let mut result = BTreeSet::new();
+
+    for path in script_files(root) {
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        let mut components = relative.components();
+        let first = components.next().map(|c| c.as_os_str().to_string_lossy().into_owned());
+
+        match (first, components.next()) {
+            (Some(dir), Some(_)) if !dir.starts_with('.') => { let _ = result.insert(dir); }
+            (Some(_), None) => return vec![".".to_owned()],
+            _ => ()
+        }
+    }
+
+    result.into_iter().collect()
+}
+
+pub fn is_ignored(contents: &str) -> bool {
+    contents.replace(" ", "").starts_with("#:ignore")
+}
+
+pub fn find_scripts_raw(root: &str) -> Vec<(String, ScriptFile)> {
     // Return both the path string and the file contents
-    let file_pairs = script_files.map(|f| {
-        let path = String::from(f.clone().path().to_string_lossy());
-        (path, read_to_string(f.path()))
+    let file_pairs = script_files(root).into_iter().map(|f| {
+        let path = String::from(f.to_string_lossy());
+        (path, read_to_string(&f))
     })
     .filter(|r| r.1.is_ok());
     // dzp ignore rule
     let unignored = file_pairs
         .map(|(p, r)| (p, r.unwrap()))
-        .filter(|r| !r.1.replace(" ", "").starts_with("#:ignore"));
+        .filter(|r| !is_ignored(&r.1));
     // Parse file contents
     let parsed_files = unignored.map(|(p, c)| (p, from_str::<ScriptFile>(&c)))
         // Validate parse
@@ -46,36 +83,55 @@ pub fn find_scripts_raw() -> Vec<(String, ScriptFile)> {
     result
 }
 
-pub fn find_scripts() -> BTreeMap<String, Script> {
-    let parsed_files = find_scripts_raw();
+/// Finds and parses every script under `root`.
+///
+/// Scripts that fail to parse are left out of the map; their errors are returned alongside it.
+pub fn find_scripts(root: &str) -> (Scripts, Vec<String>) {
+    let parsed_files = find_scripts_raw(root);
     let mut result = BTreeMap::new();
+    let mut errors = Vec::new();
 
     for (path, scripts) in parsed_files {
         for (name, contents) in scripts {
-            let s = Script::from((path.clone(), contents));
-            match s {
+            match Script::from((path.clone(), contents)) {
                 Ok(script) => { let _ = result.insert(name, script); },
-                Err(e) => err(&e, None)
+                Err(e) => errors.push(format!("{}: {}", name, e))
             }
         }
     }
 
-    result
+    (result, errors)
+}
+
+/// Returns the original text of a script from its file, from its name to the next top-level key.
+pub fn script_source(path: &str, name: &str) -> Option<String> {
+    let text = read_to_string(path).ok()?;
+    let mut lines = text.lines().skip_while(|l| l.trim_end() != format!("{}:", name));
+    let mut result = vec![lines.next()?];
+
+    result.extend(lines.take_while(|l| l.is_empty() || l.starts_with(char::is_whitespace) || l.starts_with('#')));
+
+    // Comments right before the next script belong to it
+    while result.last().is_some_and(|l| l.trim().is_empty() || l.starts_with('#')) {
+        result.pop();
+    }
+
+    Some(result.join("\n"))
 }
 
-pub fn script_contents() -> Vec<String> {
-    let scripts = find_scripts_raw();
+pub fn script_contents(root: &str) -> Vec<String> {
+    let scripts = find_scripts_raw(root);
     let mut unique = Vec::new();
 
     for file in &scripts {
-        let keys = file.1.keys().map(|s| s.clone()).collect::<Vec<String>>();
+        let keys = file.1.keys().cloned().collect::<Vec<String>>();
         let script_name = keys.first().unwrap();
 
-        if !scripts.iter().find(|(name, _)| name == script_name).is_some() {
+        if !scripts.iter().any(|(name, _)| name == script_name) {
             unique.push(file);
         }
     }
-    
+
     unique
         .iter()
         .map(|(_, script)| to_string(script))
@@ -88,47 +144,76 @@ pub fn script_contents() -> Vec<String> {
         .collect::<Vec<String>>()
 }
 
-pub fn write_scripts() -> BTreeMap<String, Script> {
-    let result = find_scripts();
+/// Compiles every script under `root` into the contents of a single file.
+pub fn compile(root: &str, project: Option<&Project>) -> String {
+    let scripts = script_contents(root).join("\n");
+    let header = match project {
+        Some(p) => p.comment_header() + "\n\n",
+        None => String::new()
+    };
+
+    format!("#: ignore\n\n{}{}", header, scripts)
+}
 
-    if Path::new("./.dzp").exists() {
+pub fn write_scripts(root: &str) -> (Scripts, Vec<String>) {
+    let (result, errors) = find_scripts(root);
+    let dir = Path::new(root).join(".dzp");
+
+    if dir.exists() {
         let ser = to_string(&result);
         if let Ok(content) = ser {
-            let _ = write("./.dzp/scripts", content);
+            let _ = write(dir.join("scripts"), content);
         }
     }
-    
-    result
+
+    (result, errors)
 }
 
-pub fn get_scripts(force: bool) -> BTreeMap<String, Script> {
+/// Returns the scripts under `root`, reading from the `.dzp/scripts` cache unless `force` is set.
+pub fn get_scripts(root: &str, force: bool) -> (Scripts, Vec<String>) {
     if force {
-        write_scripts()
+        write_scripts(root)
     } else {
-        match read_to_string("./.dzp/scripts") {
-            Ok(cache) => {
-                match from_str::<BTreeMap<String, Script>>(&cache) {
-                    Ok(data) => data,
-                    Err(_) => BTreeMap::new()
-                }
-            }
-            Err(_) => {
-                write_scripts()
-            }
+        match read_to_string(Path::new(root).join(".dzp/scripts")) {
+            Ok(cache) => (from_str::<Scripts>(&cache).unwrap_or_default(), Vec::new()),
+            Err(_) => write_scripts(root)
         }
     }
 }
 
-pub fn get_script_types() -> BTreeMap<String, Vec<String>> {
-    let scripts = get_scripts(true);
+pub fn script_types(scripts: &Scripts) -> BTreeMap<String, Vec<String>> {
     let mut result = BTreeMap::<String, Vec<String>>::new();
 
     for (name, script) in scripts {
         match result.get_mut(&script.script_type) {
-            Some(v) => v.push(name),
-            None => { let _ = result.insert(script.script_type, vec![name]); }
+            Some(v) => v.push(name.clone()),
+            None => { let _ = result.insert(script.script_type.clone(), vec![name.clone()]); }
         }
     }
 
     result
-}
\ No newline at end of file
+}
+
+/// Returns the scripts handling each event, keyed by the event name.
+pub fn script_events(scripts: &Scripts) -> BTreeMap<String, Vec<String>> {
+    let mut result = BTreeMap::<String, Vec<String>>::new();
+
+    for (name, script) in scripts {
+        for event in script.events.iter().flatten() {
+            let names = result.entry(Script::event_name(event)).or_default();
+
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns the command scripts, keyed by command name.
+pub fn script_commands(scripts: &Scripts) -> BTreeMap<String, String> {
+    scripts.iter()
+        .filter_map(|(name, script)| script.command.as_ref().map(|c| (c.name.clone(), name.clone())))
+        .collect()
+}
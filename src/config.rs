@@ -1 +1,3 @@
-pub mod styles;
\ No newline at end of file
+pub mod styles;
+pub mod templates;
+pub mod user;
\ No newline at end of file
@@ -1,36 +1,430 @@
-use std::fs::{read_to_string, write};
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::fs::{copy, read_dir, read_to_string, remove_dir_all, remove_file, write};
+use std::path::{Path, PathBuf};
 
 use dirs::config_dir;
+use walkdir::WalkDir;
 
-use crate::io::fs::create_dir;
+use crate::error::Error;
+use crate::io::fs::{copy_dir, create_all, create_dir, get_project};
+use crate::io::git::{clone, is_remote};
+use crate::structs::project::Project;
+use crate::structs::style::Style;
+
+const DEFAULT: &str = r#"description: Separate directories for data, utility and main scripts
+dirs:
+- src/data
+- src/util
+entry: src/main/{{name}}.dsc
+"#;
+
+const MAIN: &str = r#"description: A single directory for every script
+entry: src/main/{{name}}.dsc
+"#;
+
+const SINGLE: &str = r#"description: One script file in the project directory
+entry: "{{name}}.dsc"
+"#;
+
+const FULL: &str = r#"description: A world script, a command, a data config and a locale file
+dirs:
+- src/util
+files:
+  src/main/{{name}}.dsc: |
+    # {{name}} v{{version}}
+    # Authors: {{authors}}
+    {{namespace}}_world:
+      type: world
+      events:
+        after server start:
+        - debug log "<script[{{namespace}}_locale].data_key[messages.loaded]>"
+  src/commands/{{namespace}}_command.dsc: |
+    {{namespace}}_command:
+      type: command
+      name: {{namespace}}
+      description: The main command of {{name}}.
+      usage: /{{namespace}}
+      permission: {{namespace}}.command
+      script:
+      - narrate "<script[{{namespace}}_locale].data_key[messages.command]>"
+  src/data/{{namespace}}_config.dsc: |
+    {{namespace}}_config:
+      type: data
+      enabled: true
+  src/data/{{namespace}}_locale.dsc: |
+    {{namespace}}_locale:
+      type: data
+      messages:
+        loaded: {{name}} has loaded!
+        command: Hello from {{name}}!
+entry: src/main/{{name}}.dsc
+"#;
+
+/// The styles shipped with dzp, used whenever the user hasn't overridden them.
+pub const BUILTIN: [(&str, &str); 4] = [
+    ("default", DEFAULT),
+    ("main", MAIN),
+    ("single", SINGLE),
+    ("full", FULL)
+];
 
 pub fn get_dir() -> String {
     String::from(config_dir().unwrap().to_str().unwrap())
 }
 
-pub fn init_styles(force: bool) {
-    let dir = format!("{}/dzp_styles", get_dir());
+pub fn styles_dir() -> PathBuf {
+    Path::new(&get_dir()).join("dzp_styles")
+}
+
+fn style_path(style: &str) -> PathBuf {
+    styles_dir().join(format!("{}.yml", style))
+}
+
+/// The path of a style in the older format, which lists one directory per line.
+fn legacy_path(style: &str) -> PathBuf {
+    styles_dir().join(format!("{}.txt", style))
+}
+
+/// The directory of a style made of template files, with an optional `style.yml` manifest.
+fn dir_path(style: &str) -> PathBuf {
+    styles_dir().join(style)
+}
+
+/// Checks that a style name can't point outside of the styles directory.
+fn valid_name(name: &str) -> Result<(), Error> {
+    let invalid = name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\'])
+        || dir_path(name).parent() != Some(styles_dir().as_path());
 
-    if force || !Path::new(&dir).exists() {
-        create_dir(dir.as_str(), false);
-        let _ = write(format!("{}/default.txt", dir), "src/data\nsrc/util\nsrc/main");
-        let _ = write(format!("{}/main.txt", dir), "src/main");
-        let _ = write(format!("{}/single.txt", dir), ".");
+    if invalid {
+        Err(Error::new("Invalid style name.", Some(format!("'{}' would be outside of the styles directory.", name))))
+    } else {
+        Ok(())
     }
 }
 
-pub fn read_style(style: &str) -> Result<Vec<String>, String> {
-    let dir = format!("{}/dzp_styles/{}.txt", get_dir(), style);
+/// Entries of a style directory that aren't template files.
+const DIR_SKIP: [&str; 2] = [".git", "style.yml"];
 
-    match read_to_string(dir) {
-        Ok(txt) => {
-            Ok(txt
-                .split_terminator('\n')
-                .map(|s| String::from(s))
-                .collect::<Vec<String>>()
-            )
+fn builtin(style: &str) -> Option<&'static str> {
+    BUILTIN.iter()
+        .find(|(name, _)| *name == style)
+        .map(|(_, contents)| *contents)
+}
+
+/// Writes any built-in style files that are missing, leaving existing ones untouched.
+pub fn init_styles() -> Result<(), Error> {
+    let dir = styles_dir();
+    create_dir(&dir.to_string_lossy())?;
+
+    for (name, contents) in BUILTIN {
+        if !style_path(name).exists() && !legacy_path(name).exists() {
+            let _ = write(style_path(name), contents);
+        }
+    }
+
+    Ok(())
+}
+
+/// Restores a built-in style, or every built-in style if none is given, to its default.
+pub fn reset_styles(style: Option<&str>) -> Result<Vec<&'static str>, Error> {
+    let styles = match style {
+        Some(s) => match BUILTIN.iter().find(|(name, _)| *name == s) {
+            Some(found) => vec![*found],
+            None => return Err(Error::new("Not a built-in style.", Some(s.to_owned())))
         },
-        Err(e) => Err(e.to_string())
+        None => BUILTIN.to_vec()
+    };
+
+    create_dir(&styles_dir().to_string_lossy())?;
+
+    for (name, contents) in &styles {
+        write(style_path(name), contents)
+            .map_err(|e| Error::new("Failed to write style.", Some(format!("{}: {}", style_path(name).display(), e))))?;
+        let _ = remove_file(legacy_path(name));
+    }
+
+    Ok(styles.iter().map(|(name, _)| *name).collect())
+}
+
+/// Reads a style directory, adding every file in it to the manifest's files.
+fn read_dir_style(dir: &Path) -> Result<Style, Error> {
+    let mut style = match read_to_string(dir.join("style.yml")) {
+        Ok(text) => Style::from_yaml(&text)?,
+        Err(_) => Style::default()
+    };
+
+    let entries = WalkDir::new(dir).min_depth(1).into_iter()
+        .filter_entry(|e| e.depth() > 1 || !DIR_SKIP.contains(&e.file_name().to_string_lossy().as_ref()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file());
+
+    for entry in entries {
+        let path = entry.path().strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/");
+        let contents = read_to_string(entry.path())
+            .map_err(|e| Error::new("Failed to read style file.", Some(format!("{}: {}", path, e))))?;
+        style.files.entry(path).or_insert(contents);
+    }
+
+    Ok(style)
+}
+
+/// Returns where a style is stored in the config directory, if it is.
+pub fn style_location(style: &str) -> Option<PathBuf> {
+    vec![style_path(style), legacy_path(style), dir_path(style)].into_iter()
+        .find(|p| p.exists())
+}
+
+/// Returns the names of every style, including built-in styles that were removed from the config directory.
+pub fn style_names() -> BTreeSet<String> {
+    let mut names = BUILTIN.iter()
+        .map(|(name, _)| name.to_string())
+        .collect::<BTreeSet<String>>();
+
+    for entry in read_dir(styles_dir()).into_iter().flatten().flatten() {
+        let path = entry.path();
+
+        let name = if path.is_dir() {
+            path.file_name()
+        } else if path.extension().is_some_and(|e| e == "yml" || e == "txt") {
+            path.file_stem()
+        } else {
+            None
+        };
+
+        if let Some(n) = name {
+            names.insert(n.to_string_lossy().into_owned());
+        }
+    }
+
+    names
+}
+
+fn delete(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        remove_dir_all(path)
+    } else {
+        remove_file(path)
+    }
+}
+
+/// Adds a style from a manifest, a directory or a git repository.
+pub fn add_style(name: &str, source: &str) -> Result<PathBuf, Error> {
+    valid_name(name)?;
+
+    if let Some(existing) = style_location(name) {
+        return Err(Error::new("Style already exists.", Some(existing.display().to_string())));
+    }
+
+    create_dir(&styles_dir().to_string_lossy())?;
+
+    let src = Path::new(source);
+
+    let dest = if is_remote(source) {
+        let dest = dir_path(name);
+        clone(source, &dest)?;
+        dest
+    } else if src.is_dir() {
+        let dest = dir_path(name);
+        copy_dir(src, &dest, &[".git"])?;
+        dest
+    } else if src.is_file() {
+        let legacy = src.extension().is_some_and(|e| e == "txt");
+        let dest = if legacy { legacy_path(name) } else { style_path(name) };
+        copy(src, &dest).map_err(|e| Error::new("Failed to copy style.", Some(format!("{}: {}", source, e))))?;
+        dest
+    } else {
+        return Err(Error::new("Not a file, directory or git URL.", Some(source.to_owned())));
+    };
+
+    // Don't keep styles that can't be used
+    if let Err(e) = read_style(name) {
+        let _ = delete(&dest);
+        return Err(e);
+    }
+
+    Ok(dest)
+}
+
+/// Removes a style from the config directory.
+///
+/// Built-in styles can't be removed, since they would be written again on the next run.
+pub fn remove_style(name: &str) -> Result<PathBuf, Error> {
+    valid_name(name)?;
+
+    if builtin(name).is_some() {
+        return Err(Error::new("Built-in styles can't be removed.", Some(format!("Use `dzp style reset {}` to restore it instead.", name))));
+    }
+
+    let path = style_location(name).ok_or_else(|| Error::new("Style not found.", Some(name.to_owned())))?;
+
+    delete(&path)
+        .map(|_| path.clone())
+        .map_err(|e| Error::new("Failed to delete style.", Some(format!("{}: {}", path.display(), e))))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Replaces whole identifiers only, so a short name doesn't change unrelated words.
+///
+/// With `prefix`, the identifier may also be followed by an underscore, as in `namespace_command`.
+fn replace_word(text: &str, from: &str, to: &str, prefix: bool) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(i) = rest.find(from) {
+        let before = rest[..i].chars().last().or_else(|| result.chars().last());
+        let after = rest[i + from.len()..].chars().next();
+
+        let starts = !before.is_some_and(is_word_char);
+        let ends = match after {
+            Some('_') => prefix,
+            Some(c) => !is_word_char(c),
+            None => true
+        };
+
+        result += &rest[..i];
+        result += if starts && ends { to } else { from };
+        rest = &rest[i + from.len()..];
+    }
+
+    result + rest
+}
+
+/// Replaces a project's name and namespace with style variables.
+fn templatize(text: &str, name: &str, namespace: &str) -> String {
+    let mut vars = [(namespace, "{{namespace}}", true), (name, "{{name}}", false)];
+    // Replace the longer one first, in case it contains the other
+    vars.sort_by_key(|(v, _, _)| std::cmp::Reverse(v.len()));
+
+    vars.iter()
+        .filter(|(v, _, _)| !v.is_empty())
+        .fold(text.to_owned(), |t, (from, to, prefix)| replace_word(&t, from, to, *prefix))
+}
+
+/// Replaces the project's license and version in a script's leading comments with style variables.
+///
+/// This covers `#| @license` lines and `name vX` titles, where the name is expected to already be replaced.
+fn templatize_header(text: &str, project: &Project) -> String {
+    let version = project.version.to_string();
+    let mut result = String::new();
+    let mut lines = text.split_inclusive('\n');
+
+    for line in lines.by_ref() {
+        let content = line.trim_end();
+
+        let replaced = match content.strip_prefix("#| @license ") {
+            Some(license) if license.trim() == project.license => Some("#| @license {{license}}".to_owned()),
+            _ => ["#| ", "# "].iter()
+                .find(|p| content.strip_prefix(*p).and_then(|t| t.strip_prefix("{{name}} v")) == Some(&version))
+                .map(|p| format!("{}{{{{name}}}} v{{{{version}}}}", p))
+        };
+
+        match replaced {
+            Some(r) => result += &(r + &line[content.len()..]),
+            None => result += line
+        }
+
+        if !content.is_empty() && !content.starts_with('#') {
+            break;
+        }
+    }
+
+    result + &lines.collect::<String>()
+}
+
+/// Returns the script a style's files start from: the one named after the project, or else the first world script.
+fn find_entry(files: &[(String, String)]) -> Option<String> {
+    let scripts = files.iter().filter(|(path, _)| path.ends_with(".dsc"));
+    let named = |(path, _): &&(String, String)| {
+        let file = path.rsplit('/').next().unwrap_or_default();
+        file == "{{name}}.dsc" || file == "{{namespace}}.dsc"
+    };
+
+    scripts.clone().find(named)
+        .or_else(|| scripts.clone().find(|(_, text)| text.lines().any(|l| l.trim() == "type: world")))
+        .map(|(path, _)| path.clone())
+}
+
+/// Creates a style directory from an existing project, replacing its name and namespace with variables.
+///
+/// Returns the style's path along with the files that were left out for not being text.
+pub fn create_style(name: &str, project_dir: &str) -> Result<(PathBuf, Vec<String>), Error> {
+    valid_name(name)?;
+
+    if let Some(existing) = style_location(name) {
+        return Err(Error::new("Style already exists.", Some(existing.display().to_string())));
+    }
+
+    let project = get_project(Some(project_dir))?;
+    let namespace = project.namespace_default();
+    let root = Path::new(project_dir);
+
+    let mut files = Vec::new();
+    let mut dirs = Vec::new();
+    let mut skipped = Vec::new();
+
+    // Files that `dzp new` creates for every project are left out
+    let skip = [".git", ".dzp", "README.md"];
+    let entries = WalkDir::new(root).min_depth(1).sort_by_file_name().into_iter()
+        .filter_entry(|e| {
+            let file = e.file_name().to_string_lossy();
+            e.depth() > 1 || !(skip.contains(&file.as_ref()) || file.starts_with("LICENSE"))
+        })
+        .filter_map(|e| e.ok());
+
+    for entry in entries {
+        let path = entry.path().strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/");
+        let path = templatize(&path, &project.name, &namespace);
+
+        if entry.file_type().is_dir() {
+            if read_dir(entry.path()).is_ok_and(|mut d| d.next().is_none()) {
+                dirs.push(path);
+            }
+        } else if let Ok(text) = read_to_string(entry.path()) {
+            let text = templatize(&text, &project.name, &namespace);
+            let text = if path.ends_with(".dsc") { templatize_header(&text, &project) } else { text };
+            files.push((path, text));
+        } else {
+            skipped.push(path);
+        }
+    }
+
+    let style = Style {
+        description: Some(format!("Created from {}", project.name)),
+        dirs,
+        entry: find_entry(&files),
+        ..Style::default()
+    };
+
+    let dest = dir_path(name);
+    create_dir(&dest.to_string_lossy())?;
+    create_all(&dest.to_string_lossy(), files)?;
+
+    let manifest = serde_yaml::to_string(&style).unwrap_or_default();
+    write(dest.join("style.yml"), manifest.trim_start_matches("---\n"))
+        .map_err(|e| Error::new("Failed to create file.", Some(format!("{}: {}", dest.join("style.yml").display(), e))))?;
+
+    Ok((dest, skipped))
+}
+
+/// Reads a style from the config directory, falling back to the built-in style of the same name.
+pub fn read_style(style: &str) -> Result<Style, Error> {
+    if let Ok(text) = read_to_string(style_path(style)) {
+        return Style::from_yaml(&text);
+    }
+
+    if let Ok(text) = read_to_string(legacy_path(style)) {
+        return Ok(Style::from_dirs(&text));
+    }
+
+    if dir_path(style).is_dir() {
+        return read_dir_style(&dir_path(style));
+    }
+
+    match builtin(style) {
+        Some(text) => Style::from_yaml(text),
+        None => Err(Error::new("Style not found.", Some(format!("No style named '{}' in {}.", style, styles_dir().display()))))
     }
 }
\ No newline at end of file
@@ -0,0 +1,19 @@
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use super::styles::get_dir;
+
+pub fn global_readme() -> PathBuf {
+    Path::new(&get_dir()).join("dzp/readme.tpl")
+}
+
+pub fn project_readme(root: &str) -> PathBuf {
+    Path::new(root).join(".dzp/readme.tpl")
+}
+
+/// Reads the project's README template, falling back to the global one.
+pub fn read_readme(root: &str) -> Option<String> {
+    read_to_string(project_readme(root))
+        .or_else(|_| read_to_string(global_readme()))
+        .ok()
+}
@@ -0,0 +1,74 @@
+use std::env::var_os;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+use url::Url;
+
+use super::styles::get_dir;
+use crate::error::Error;
+use crate::structs::project::Preset;
+
+/// The user's defaults, read from `dzp/config.yml` in the config directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserConfig {
+    pub authors: Option<Vec<String>>,
+    pub license: Option<String>,
+    /// The user's name on their git host, for the repository pattern.
+    pub user: Option<String>,
+    /// The repository URL of a project, with `{user}` and `{name}` filled in.
+    pub repository: Option<String>,
+    /// The style `dzp new` uses without `--style`.
+    pub style: Option<String>,
+    /// Whether to color output. Defaults to on unless `NO_COLOR` is set.
+    pub color: Option<bool>,
+    /// Whether `dzp new` initializes a git repository.
+    pub git: Option<bool>
+}
+
+impl UserConfig {
+    /// Fills in the repository pattern for a project. `{user}` becomes `<user>` if the user isn't set.
+    pub fn repository_pattern(&self, name: &str) -> Option<String> {
+        let user = self.user.as_deref().unwrap_or("<user>");
+
+        self.repository.as_ref().map(|r| r.replace("{user}", user).replace("{name}", name))
+    }
+
+    /// Returns the repository URL of a project, if the pattern gives a complete one.
+    pub fn repository_url(&self, name: &str) -> Option<Url> {
+        self.repository_pattern(name)
+            .filter(|r| !r.contains('<'))
+            .and_then(|r| Url::parse(&r).ok())
+    }
+
+    /// Returns the defaults for a new project.
+    pub fn defaults(&self, name: &str) -> Preset {
+        Preset {
+            authors: self.authors.clone(),
+            license: self.license.clone(),
+            repository: self.repository_url(name),
+            ..Preset::default()
+        }
+    }
+
+    pub fn color(&self) -> bool {
+        self.color.unwrap_or_else(|| var_os("NO_COLOR").is_none())
+    }
+
+    pub fn git(&self) -> bool {
+        self.git.unwrap_or(true)
+    }
+}
+
+pub fn config_path() -> PathBuf {
+    Path::new(&get_dir()).join("dzp/config.yml")
+}
+
+/// Reads the user's configuration, which is empty if the file doesn't exist.
+pub fn read_config() -> Result<UserConfig, Error> {
+    match read_to_string(config_path()) {
+        Ok(text) => serde_yaml::from_str(&text)
+            .map_err(|e| Error::new("Failed to read config.", Some(format!("{}: {}", config_path().display(), e)))),
+        Err(_) => Ok(UserConfig::default())
+    }
+}
@@ -0,0 +1,14 @@
+pub mod html;
+pub mod json;
+pub mod markdown;
+
+/// A generated file, as a path relative to the output directory and its contents.
+pub type Output = Vec<(String, String)>;
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,405 @@
+use std::collections::BTreeMap;
+
+use titlecase::titlecase;
+
+use super::{escape_html as esc, Output};
+use crate::cache::scripts::{script_commands, script_events, script_types, Scripts};
+use crate::structs::command::Command;
+use crate::structs::doc::{Doc, DocArg, Example};
+use crate::structs::project::Project;
+use crate::structs::script::Script;
+
+const STYLE: &str = r#"body {
+  font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif;
+  max-width: 52rem;
+  margin: 2rem auto;
+  padding: 0 1rem;
+  color: #24292f;
+  line-height: 1.5;
+}
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+code, pre { font-family: ui-monospace, Consolas, monospace; }
+pre { background: #f6f8fa; padding: 0.75rem; border-radius: 6px; overflow-x: auto; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #d0d7de; padding: 0.4rem 0.6rem; text-align: left; }
+.badge { display: inline-block; font-size: 0.75rem; padding: 0.1rem 0.5rem; border-radius: 1rem; background: #ddf4ff; vertical-align: middle; }
+.badge.deprecated, p.deprecated { background: #fff8c5; color: #9a6700; }
+p.deprecated { padding: 0.5rem 0.75rem; border-radius: 6px; }
+dl { display: grid; grid-template-columns: max-content auto; gap: 0.25rem 1rem; }
+dt { font-weight: bold; }
+dd { margin: 0; }
+.path { color: #57606a; }
+#search { width: 100%; padding: 0.5rem; font-size: 1rem; margin-bottom: 1rem; box-sizing: border-box; }
+"#;
+
+const SEARCH: &str = r#"const input = document.getElementById("search");
+
+input.addEventListener("input", () => {
+  const query = input.value.trim().toLowerCase();
+
+  for (const group of document.querySelectorAll(".group")) {
+    let visible = 0;
+
+    for (const item of group.querySelectorAll("li")) {
+      const match = item.dataset.search.includes(query);
+      item.hidden = !match;
+      if (match) visible++;
+    }
+
+    group.hidden = visible === 0;
+  }
+});
+"#;
+
+fn page(title: &str, root: &str, body: String) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"{}style.css\">\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        esc(title), root, body
+    )
+}
+
+fn script_link(name: &str, scripts: &Scripts, root: &str) -> String {
+    if scripts.contains_key(name) {
+        format!("<a href=\"{}scripts/{}.html\"><code>{}</code></a>", root, esc(name), esc(name))
+    } else {
+        format!("<code>{}</code>", esc(name))
+    }
+}
+
+fn deprecated_badge(doc: Option<&Doc>) -> &'static str {
+    if doc.is_some_and(Doc::is_deprecated) {
+        " <span class=\"badge deprecated\">deprecated</span>"
+    } else {
+        ""
+    }
+}
+
+fn arg_type(arg: &DocArg) -> String {
+    match &arg.arg_type {
+        Some(t) => format!("<code>{}</code>", esc(t)),
+        None => String::new()
+    }
+}
+
+fn arg_table(title: &str, args: &BTreeMap<String, DocArg>) -> String {
+    let rows = args.iter()
+        .map(|(name, arg)| format!(
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+            esc(name),
+            arg_type(arg),
+            esc(&arg.description.clone().unwrap_or_default())
+        ))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("<h2>{}</h2>\n<table>\n<tr><th>Name</th><th>Type</th><th>Description</th></tr>\n{}\n</table>", title, rows)
+}
+
+fn link_list(title: &str, names: &[String], scripts: &Scripts) -> String {
+    let links = names.iter()
+        .map(|n| format!("<li>{}</li>", script_link(n, scripts, "../")))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("<h2>{}</h2>\n<ul>\n{}\n</ul>", title, links)
+}
+
+fn deprecation(doc: &Doc, scripts: &Scripts) -> String {
+    let notice = doc.deprecated.as_ref();
+    let reason = notice.and_then(|d| d.reason()).map(|r| format!(" {}", esc(r))).unwrap_or_default();
+    let replacement = notice.and_then(|d| d.replacement())
+        .map(|r| format!(" Use {} instead.", script_link(r, scripts, "../")))
+        .unwrap_or_default();
+
+    format!("<p class=\"deprecated\"><strong>Deprecated.</strong>{}{}</p>", reason, replacement)
+}
+
+fn details(doc: &Doc) -> Option<String> {
+    let mut fields = Vec::new();
+
+    if let Some(since) = &doc.since {
+        fields.push(format!("<dt>Since</dt><dd>{}</dd>", since));
+    }
+
+    if let Some(author) = &doc.author {
+        fields.push(format!("<dt>Author</dt><dd>{}</dd>", esc(author)));
+    }
+
+    if let Some(tags) = &doc.tags {
+        let badges = tags.iter()
+            .map(|t| format!("<span class=\"badge\">{}</span>", esc(t)))
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        fields.push(format!("<dt>Tags</dt><dd>{}</dd>", badges));
+    }
+
+    if fields.is_empty() {
+        None
+    } else {
+        Some(format!("<dl>\n{}\n</dl>", fields.join("\n")))
+    }
+}
+
+fn examples(examples: &[Example]) -> String {
+    let blocks = examples.iter()
+        .map(|e| {
+            let code = format!("<pre><code>{}</code></pre>", esc(e.code().trim_end()));
+
+            match e.caption() {
+                Some(c) => format!("<p><em>{}</em></p>\n{}", esc(c), code),
+                None => code
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("<h2>Examples</h2>\n{}", blocks)
+}
+
+fn doc_sections(doc: &Doc, scripts: &Scripts) -> Vec<String> {
+    let mut sections = Vec::new();
+
+    if doc.is_deprecated() {
+        sections.push(deprecation(doc, scripts));
+    }
+
+    if let Some(description) = &doc.description {
+        sections.push(format!("<p>{}</p>", esc(description)));
+    }
+
+    if let Some(d) = details(doc) {
+        sections.push(d);
+    }
+
+    if let Some(usage) = &doc.usage {
+        sections.push(format!("<h2>Usage</h2>\n<pre><code>{}</code></pre>", esc(usage)));
+    }
+
+    if let Some(e) = &doc.examples {
+        sections.push(examples(e));
+    }
+
+    if let Some(determine) = &doc.determine {
+        let desc = match &determine.description {
+            Some(d) => format!(" {}", esc(d)),
+            None => String::new()
+        };
+
+        sections.push(format!("<h2>Determine</h2>\n<p>{}{}</p>", arg_type(determine), desc));
+    }
+
+    if let Some(defs) = &doc.defs {
+        sections.push(arg_table("Definitions", defs));
+    }
+
+    if let Some(keys) = &doc.keys {
+        sections.push(arg_table("Data Keys", keys));
+    }
+
+    if let Some(uses) = &doc.uses {
+        sections.push(link_list("Uses", uses, scripts));
+    }
+
+    if let Some(see) = &doc.see {
+        sections.push(link_list("See Also", see, scripts));
+    }
+
+    sections
+}
+
+fn events(events: &[String]) -> String {
+    let items = events.iter()
+        .map(|e| format!("<li><code>{}</code></li>", esc(e)))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("<h2>Events</h2>\n<ul>\n{}\n</ul>", items)
+}
+
+fn command(command: &Command) -> String {
+    let mut rows = vec![format!("<dt>Command</dt><dd><code>/{}</code></dd>", esc(&command.name))];
+
+    if !command.aliases.is_empty() {
+        let aliases = command.labels()[1..].iter()
+            .map(|a| format!("<code>{}</code>", esc(a)))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        rows.push(format!("<dt>Aliases</dt><dd>{}</dd>", aliases));
+    }
+
+    if let Some(usage) = &command.usage {
+        rows.push(format!("<dt>Usage</dt><dd><code>{}</code></dd>", esc(usage)));
+    }
+
+    if let Some(permission) = &command.permission {
+        rows.push(format!("<dt>Permission</dt><dd><code>{}</code></dd>", esc(permission)));
+    }
+
+    if let Some(description) = &command.description {
+        rows.push(format!("<dt>Help</dt><dd>{}</dd>", esc(description)));
+    }
+
+    format!("<h2>Command</h2>\n<dl>\n{}\n</dl>", rows.join("\n"))
+}
+
+fn script_page(name: &str, script: &Script, scripts: &Scripts, used_by: &[String]) -> String {
+    let mut sections = vec![
+        "<p><a href=\"../index.html\">&larr; All scripts</a></p>".to_owned(),
+        format!(
+            "<h1><code>{}</code> <span class=\"badge\">{}</span>{}</h1>",
+            esc(name),
+            esc(&script.script_type),
+            deprecated_badge(script.doc.as_ref())
+        ),
+        format!("<p class=\"path\">{}</p>", esc(&script.path))
+    ];
+
+    match &script.doc {
+        Some(doc) => sections.extend(doc_sections(doc, scripts)),
+        None => sections.push("<p><em>No documentation.</em></p>".to_owned())
+    }
+
+    if let Some(c) = &script.command {
+        sections.push(command(c));
+    }
+
+    if let Some(e) = &script.events {
+        sections.push(events(e));
+    }
+
+    if !used_by.is_empty() {
+        sections.push(link_list("Used By", used_by, scripts));
+    }
+
+    page(name, "../", sections.join("\n"))
+}
+
+fn event_index(scripts: &Scripts) -> Option<String> {
+    let events = script_events(scripts);
+
+    if events.is_empty() {
+        return None;
+    }
+
+    let items = events.iter()
+        .map(|(event, names)| {
+            let links = names.iter()
+                .map(|n| script_link(n, scripts, ""))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            format!(
+                "<li data-search=\"{}\"><code>{}</code> &mdash; {}</li>",
+                esc(&format!("{} {}", event, names.join(" ")).to_lowercase()),
+                esc(event),
+                links
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    Some(format!("<section class=\"group\">\n<h2>Events Handled</h2>\n<ul>\n{}\n</ul>\n</section>", items))
+}
+
+fn command_index(scripts: &Scripts) -> Option<String> {
+    let commands = script_commands(scripts);
+
+    if commands.is_empty() {
+        return None;
+    }
+
+    let items = commands.values()
+        .filter_map(|name| scripts.get(name).and_then(|s| s.command.as_ref()).map(|c| (name, c)))
+        .map(|(name, c)| {
+            let labels = c.labels();
+            let description = c.description.as_ref()
+                .map(|d| format!(" &mdash; {}", esc(d)))
+                .unwrap_or_default();
+
+            format!(
+                "<li data-search=\"{}\"><code>{}</code> ({}){}</li>",
+                esc(&format!("{} {} {}", labels.join(" "), name, c.description.clone().unwrap_or_default()).to_lowercase()),
+                esc(&labels.join(", ")),
+                script_link(name, scripts, ""),
+                description
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    Some(format!("<section class=\"group\">\n<h2>Commands</h2>\n<ul>\n{}\n</ul>\n</section>", items))
+}
+
+fn index(title: &str, description: Option<&str>, scripts: &Scripts) -> String {
+    let groups = script_types(scripts).iter()
+        .map(|(script_type, names)| {
+            let items = names.iter()
+                .map(|name| {
+                    let doc = scripts.get(name).and_then(|s| s.doc.as_ref());
+                    let description = doc.and_then(|d| d.description.clone()).unwrap_or_default();
+                    let summary = if description.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" &mdash; {}", esc(&description))
+                    };
+
+                    format!(
+                        "<li data-search=\"{}\">{}{}{}</li>",
+                        esc(&format!("{} {}", name, description).to_lowercase()),
+                        script_link(name, scripts, ""),
+                        deprecated_badge(doc),
+                        summary
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            format!("<section class=\"group\">\n<h2>{}</h2>\n<ul>\n{}\n</ul>\n</section>", esc(&titlecase(script_type)), items)
+        })
+        .chain(command_index(scripts))
+        .chain(event_index(scripts))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let description = match description {
+        Some(d) if !d.is_empty() => format!("<p>{}</p>\n", esc(d)),
+        _ => String::new()
+    };
+
+    let body = format!(
+        "<h1>{}</h1>\n{}<input id=\"search\" type=\"search\" placeholder=\"Search scripts...\">\n{}\n<script src=\"search.js\"></script>",
+        esc(title), description, groups
+    );
+
+    page(title, "", body)
+}
+
+/// Renders a static documentation site for the given scripts.
+pub fn generate(project: Option<&Project>, scripts: &Scripts) -> Output {
+    let title = project.map(|p| p.title()).unwrap_or_else(|| "Scripts".to_owned());
+    let description = project.map(|p| p.description.as_str());
+
+    let mut used_by = BTreeMap::<&str, Vec<String>>::new();
+
+    for (name, script) in scripts {
+        for u in script.doc.as_ref().and_then(|d| d.uses.as_ref()).into_iter().flatten() {
+            used_by.entry(u.as_str()).or_default().push(name.clone());
+        }
+    }
+
+    let mut result = vec![
+        ("index.html".to_owned(), index(&title, description, scripts)),
+        ("style.css".to_owned(), STYLE.to_owned()),
+        ("search.js".to_owned(), SEARCH.to_owned())
+    ];
+
+    for (name, script) in scripts {
+        let users = used_by.get(name.as_str()).cloned().unwrap_or_default();
+        result.push((format!("scripts/{}.html", name), script_page(name, script, scripts, &users)));
+    }
+
+    result
+}
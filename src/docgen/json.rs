@@ -0,0 +1,31 @@
+use serde::Serialize;
+use serde_json::to_string_pretty;
+
+use super::Output;
+use crate::cache::scripts::Scripts;
+use crate::structs::project::Project;
+
+/// The version of the JSON export format, bumped on breaking changes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+pub struct Export<'a> {
+    pub schema: u32,
+    pub project: Option<&'a Project>,
+    pub scripts: &'a Scripts
+}
+
+/// Renders every script and its kDoc as JSON.
+pub fn document(project: Option<&Project>, scripts: &Scripts) -> String {
+    let export = Export {
+        schema: SCHEMA_VERSION,
+        project,
+        scripts
+    };
+
+    to_string_pretty(&export).unwrap_or_default() + "\n"
+}
+
+pub fn generate(project: Option<&Project>, scripts: &Scripts) -> Output {
+    vec![("scripts.json".to_owned(), document(project, scripts))]
+}
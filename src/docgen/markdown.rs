@@ -0,0 +1,338 @@
+use std::collections::BTreeMap;
+
+use titlecase::titlecase;
+
+use super::Output;
+use crate::cache::scripts::{script_commands, script_events, script_types, Scripts};
+use crate::structs::command::Command;
+use crate::structs::doc::{Doc, DocArg, Example};
+use crate::structs::project::Project;
+use crate::structs::script::Script;
+
+/// How links between scripts are written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Links {
+    /// Headings in the same document.
+    Anchors,
+    /// Sibling `<name>.md` files.
+    Files,
+    /// `scripts/<name>.md` files, from the index.
+    Index
+}
+
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+pub fn script_link(name: &str, scripts: &Scripts, links: Links) -> String {
+    if !scripts.contains_key(name) {
+        return format!("`{}`", name);
+    }
+
+    match links {
+        Links::Anchors => format!("[`{}`](#{})", name, name.to_lowercase()),
+        Links::Files => format!("[`{}`]({}.md)", name, name),
+        Links::Index => format!("[`{}`](scripts/{}.md)", name, name)
+    }
+}
+
+fn arg_type(arg: &DocArg) -> String {
+    match &arg.arg_type {
+        Some(t) => format!("`{}`", t),
+        None => String::new()
+    }
+}
+
+pub fn arg_table(title: &str, args: &BTreeMap<String, DocArg>) -> String {
+    let rows = args.iter()
+        .map(|(name, arg)| format!(
+            "| `{}` | {} | {} |",
+            name,
+            cell(&arg_type(arg)),
+            cell(&arg.description.clone().unwrap_or_default())
+        ))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("**{}:**\n\n| Name | Type | Description |\n| --- | --- | --- |\n{}", title, rows)
+}
+
+pub fn determine(arg: &DocArg) -> String {
+    let desc = match &arg.description {
+        Some(d) => format!(" — {}", d),
+        None => String::new()
+    };
+
+    format!("**Determine:** {}{}", arg_type(arg), desc)
+}
+
+pub fn usage(usage: &str) -> String {
+    format!("**Usage:**\n\n```\n{}\n```", usage)
+}
+
+pub fn deprecation(doc: &Doc, scripts: &Scripts, links: Links) -> String {
+    let notice = doc.deprecated.as_ref();
+    let reason = notice.and_then(|d| d.reason()).map(|r| format!(" {}", r)).unwrap_or_default();
+    let replacement = notice.and_then(|d| d.replacement())
+        .map(|r| format!(" Use {} instead.", script_link(r, scripts, links)))
+        .unwrap_or_default();
+
+    format!("> **Deprecated.**{}{}", reason, replacement)
+}
+
+pub fn examples(examples: &[Example]) -> String {
+    let blocks = examples.iter()
+        .map(|e| {
+            let code = format!("```\n{}\n```", e.code().trim_end());
+
+            match e.caption() {
+                Some(c) => format!("*{}*\n\n{}", c, code),
+                None => code
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    format!("**Examples:**\n\n{}", blocks)
+}
+
+/// Returns the `since`, `author` and `tags` fields on one line, if any are set.
+pub fn details(doc: &Doc) -> Option<String> {
+    let mut fields = Vec::new();
+
+    if let Some(since) = &doc.since {
+        fields.push(format!("**Since:** {}", since));
+    }
+
+    if let Some(author) = &doc.author {
+        fields.push(format!("**Author:** {}", author));
+    }
+
+    if let Some(tags) = &doc.tags {
+        fields.push(format!("**Tags:** {}", tags.join(", ")));
+    }
+
+    if fields.is_empty() {
+        None
+    } else {
+        Some(fields.join(" · "))
+    }
+}
+
+pub fn link_list(title: &str, names: &[String], scripts: &Scripts, links: Links) -> String {
+    let list = names.iter()
+        .map(|n| script_link(n, scripts, links))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("**{}:** {}", title, list)
+}
+
+pub fn doc_sections(doc: &Doc, scripts: &Scripts, links: Links) -> Vec<String> {
+    let mut sections = Vec::new();
+
+    if let Some(description) = &doc.description {
+        sections.push(description.clone());
+    }
+
+    if let Some(d) = details(doc) {
+        sections.push(d);
+    }
+
+    if let Some(u) = &doc.usage {
+        sections.push(usage(u));
+    }
+
+    if let Some(e) = &doc.examples {
+        sections.push(examples(e));
+    }
+
+    if let Some(d) = &doc.determine {
+        sections.push(determine(d));
+    }
+
+    if let Some(defs) = &doc.defs {
+        sections.push(arg_table("Definitions", defs));
+    }
+
+    if let Some(keys) = &doc.keys {
+        sections.push(arg_table("Data Keys", keys));
+    }
+
+    if let Some(uses) = &doc.uses {
+        sections.push(link_list("Uses", uses, scripts, links));
+    }
+
+    if let Some(see) = &doc.see {
+        sections.push(link_list("See also", see, scripts, links));
+    }
+
+    sections
+}
+
+fn events(events: &[String]) -> String {
+    let items = events.iter()
+        .map(|e| format!("- `{}`", e))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("**Events:**\n\n{}", items)
+}
+
+fn command(command: &Command) -> String {
+    let mut fields = vec![format!("**Command:** `/{}`", command.name)];
+
+    if !command.aliases.is_empty() {
+        let aliases = command.labels()[1..].iter()
+            .map(|a| format!("`{}`", a))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        fields.push(format!("**Aliases:** {}", aliases));
+    }
+
+    if let Some(usage) = &command.usage {
+        fields.push(format!("**Usage:** `{}`", usage));
+    }
+
+    if let Some(permission) = &command.permission {
+        fields.push(format!("**Permission:** `{}`", permission));
+    }
+
+    fields.join(" · ")
+}
+
+pub fn script(name: &str, script: &Script, scripts: &Scripts, level: usize, links: Links) -> String {
+    let mut sections = vec![format!("{} `{}`", "#".repeat(level), name)];
+
+    if let Some(doc) = script.doc.as_ref().filter(|d| d.is_deprecated()) {
+        sections.push(deprecation(doc, scripts, links));
+    }
+
+    sections.push(format!("**Type:** {} · **Path:** `{}`", script.script_type, script.path));
+
+    if let Some(c) = &script.command {
+        sections.push(command(c));
+    }
+
+    match &script.doc {
+        Some(doc) => sections.extend(doc_sections(doc, scripts, links)),
+        None => sections.push("*No documentation.*".to_owned())
+    }
+
+    if let Some(e) = &script.events {
+        sections.push(events(e));
+    }
+
+    sections.join("\n\n")
+}
+
+fn header(project: Option<&Project>) -> String {
+    match project {
+        Some(p) => format!("# {}\n\n{}", p.title(), p.description_default()),
+        None => "# Scripts".to_owned()
+    }
+}
+
+fn command_index(scripts: &Scripts, links: Links) -> Option<String> {
+    let commands = script_commands(scripts);
+
+    if commands.is_empty() {
+        return None;
+    }
+
+    let rows = commands.values()
+        .filter_map(|name| scripts.get(name).and_then(|s| s.command.as_ref()).map(|c| (name, c)))
+        .map(|(name, c)| format!(
+            "| {} | {} | {} | {} |",
+            c.labels().iter().map(|l| format!("`{}`", l)).collect::<Vec<String>>().join(", "),
+            script_link(name, scripts, links),
+            c.permission.as_ref().map(|p| format!("`{}`", p)).unwrap_or_default(),
+            cell(c.description.as_deref().unwrap_or_default())
+        ))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    Some(format!("## Commands\n\n| Command | Script | Permission | Description |\n| --- | --- | --- | --- |\n{}", rows))
+}
+
+fn event_index(scripts: &Scripts, links: Links) -> Option<String> {
+    let events = script_events(scripts);
+
+    if events.is_empty() {
+        return None;
+    }
+
+    let rows = events.iter()
+        .map(|(event, names)| format!(
+            "| `{}` | {} |",
+            cell(event),
+            names.iter().map(|n| script_link(n, scripts, links)).collect::<Vec<String>>().join(", ")
+        ))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    Some(format!("## Events Handled\n\n| Event | Scripts |\n| --- | --- |\n{}", rows))
+}
+
+/// Renders every script into a single Markdown document.
+pub fn document(project: Option<&Project>, scripts: &Scripts) -> String {
+    let groups = script_types(scripts).iter()
+        .map(|(script_type, names)| {
+            let entries = names.iter()
+                .filter_map(|name| scripts.get(name).map(|s| script(name, s, scripts, 3, Links::Anchors)))
+                .collect::<Vec<String>>()
+                .join("\n\n");
+
+            format!("## {}\n\n{}", titlecase(script_type), entries)
+        })
+        .chain(command_index(scripts, Links::Anchors))
+        .chain(event_index(scripts, Links::Anchors))
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    format!("{}\n\n{}\n", header(project), groups)
+}
+
+fn index(project: Option<&Project>, scripts: &Scripts) -> String {
+    let groups = script_types(scripts).iter()
+        .map(|(script_type, names)| {
+            let entries = names.iter()
+                .map(|name| {
+                    let description = scripts.get(name)
+                        .and_then(|s| s.doc.as_ref())
+                        .and_then(|d| d.description.clone());
+
+                    match description {
+                        Some(d) => format!("- {} — {}", script_link(name, scripts, Links::Index), d),
+                        None => format!("- {}", script_link(name, scripts, Links::Index))
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            format!("## {}\n\n{}", titlecase(script_type), entries)
+        })
+        .chain(command_index(scripts, Links::Index))
+        .chain(event_index(scripts, Links::Index))
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    format!("{}\n\n{}\n", header(project), groups)
+}
+
+/// Renders Markdown documentation, either as one `scripts.md` file or as an index with one file per script
+/// in the `scripts` directory, so a script named `index` can't replace the index.
+pub fn generate(project: Option<&Project>, scripts: &Scripts, split: bool) -> Output {
+    if !split {
+        return vec![("scripts.md".to_owned(), document(project, scripts))];
+    }
+
+    let mut result = vec![("index.md".to_owned(), index(project, scripts))];
+
+    for (name, s) in scripts {
+        result.push((format!("scripts/{}.md", name), script(name, s, scripts, 1, Links::Files) + "\n"));
+    }
+
+    result
+}
@@ -0,0 +1,33 @@
+use std::fmt;
+
+use crate::io::log::err;
+
+#[derive(Debug, Clone)]
+pub struct Error {
+    pub text: String,
+    pub reason: Option<String>
+}
+
+impl Error {
+    pub fn new(text: &str, reason: Option<String>) -> Self {
+        Self {
+            text: text.to_owned(),
+            reason
+        }
+    }
+
+    pub fn log(&self) {
+        err(&self.text, self.reason.clone())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            Some(r) => write!(f, "{} {}", self.text, r),
+            None => write!(f, "{}", self.text)
+        }
+    }
+}
+
+impl std::error::Error for Error {}
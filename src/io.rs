@@ -1,2 +1,9 @@
+pub mod changelog;
+pub mod date;
 pub mod fs;
-pub mod log;
\ No newline at end of file
+pub mod git;
+pub mod header;
+pub mod highlight;
+pub mod log;
+pub mod template;
+pub mod yaml;
\ No newline at end of file
@@ -0,0 +1,62 @@
+//! Updating of `CHANGELOG.md` files in the Keep a Changelog format.
+
+/// Returns whether a line is the heading of the `Unreleased` section.
+fn is_unreleased(line: &str) -> bool {
+    let heading = line.trim().to_lowercase();
+    heading == "## [unreleased]" || heading == "## unreleased"
+}
+
+/// Adds a section for a release.
+///
+/// An `Unreleased` section becomes the release, with a new empty one above it.
+/// Otherwise, the release is added before the first section.
+pub fn add_release(text: &str, version: &str, date: &str) -> String {
+    let heading = format!("## [{}] - {}", version, date);
+
+    if text.trim().is_empty() {
+        return format!("# Changelog\n\n{}\n", heading);
+    }
+
+    let mut lines = text.lines().map(String::from).collect::<Vec<String>>();
+
+    if let Some(i) = lines.iter().position(|l| is_unreleased(l)) {
+        lines[i] = heading;
+        lines.insert(i, String::new());
+        lines.insert(i, "## [Unreleased]".to_owned());
+    } else if let Some(i) = lines.iter().position(|l| l.starts_with("## ")) {
+        lines.insert(i, String::new());
+        lines.insert(i, heading);
+    } else {
+        lines.push(String::new());
+        lines.push(heading);
+    }
+
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::add_release;
+
+    #[test]
+    fn releases_the_unreleased_section() {
+        let text = "# Changelog\n\n## [Unreleased]\n- Fixed things\n\n## [1.0.0] - 2024-01-01\n";
+        let expected = "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2024-02-01\n- Fixed things\n\n## [1.0.0] - 2024-01-01\n";
+
+        assert_eq!(add_release(text, "1.1.0", "2024-02-01"), expected);
+    }
+
+    #[test]
+    fn adds_a_section_without_an_unreleased_one() {
+        let text = "# Changelog\n\n## [1.0.0] - 2024-01-01\n- Added things\n";
+        let expected = "# Changelog\n\n## [1.1.0] - 2024-02-01\n\n## [1.0.0] - 2024-01-01\n- Added things\n";
+
+        assert_eq!(add_release(text, "1.1.0", "2024-02-01"), expected);
+    }
+
+    #[test]
+    fn adds_a_section_without_any_sections() {
+        assert_eq!(add_release("# Changelog\n", "1.0.0", "2024-01-01"), "# Changelog\n\n## [1.0.0] - 2024-01-01\n");
+        assert_eq!(add_release("", "1.0.0", "2024-01-01"), "# Changelog\n\n## [1.0.0] - 2024-01-01\n");
+    }
+}
@@ -1,42 +1,138 @@
-use std::fs::{create_dir_all, read_to_string, write};
+use std::fs::{copy, create_dir_all, read, read_to_string, remove_dir_all, remove_file, write};
+use std::path::{Path, PathBuf};
 
 use serde_yaml::from_str;
+use walkdir::WalkDir;
 
-use super::log::err;
+use crate::error::Error;
 use crate::structs::project::Project;
 
-pub fn debug_path(path: &str, debug: bool) {
-    if debug {
-        println!("Creating {}...", path);
-    }
+pub fn create_dir(path: &str) -> Result<(), Error> {
+    create_dir_all(path).map_err(|e| Error::new("Failed to create directory.", Some(format!("{}: {}", path, e))))
+}
+
+pub fn create(path: &str, content: String) -> Result<(), Error> {
+    write(path, content).map_err(|e| Error::new("Failed to create file.", Some(format!("{}: {}", path, e))))
 }
 
-pub fn create_dir(path: &str, debug: bool) {
-    debug_path(path, debug);
+/// Creates files in `dir`, along with their parent directories, stopping at the first failure.
+pub fn create_all(dir: &str, files: Vec<(String, String)>) -> Result<(), Error> {
+    for (file, content) in files {
+        let path = Path::new(dir).join(file);
+
+        if let Some(parent) = path.parent() {
+            create_dir(&parent.to_string_lossy())?;
+        }
 
-    match create_dir_all(path) {
-        Ok(_) => (),
-        Err(e) => err("Failed to create directory.", Some(e.to_string()))
+        create(&path.to_string_lossy(), content)?;
     }
+
+    Ok(())
 }
 
-pub fn create(path: &str, content: String, debug: bool) {
-    debug_path(path, debug);
+/// Copies a directory's contents into `dest`, leaving out any top-level entries named in `skip`.
+pub fn copy_dir(src: &Path, dest: &Path, skip: &[&str]) -> Result<(), Error> {
+    let failed = |e: String| Error::new("Failed to copy directory.", Some(format!("{}: {}", src.display(), e)));
+
+    create_dir_all(dest).map_err(|e| failed(e.to_string()))?;
+
+    let entries = WalkDir::new(src).min_depth(1).into_iter()
+        .filter_entry(|e| e.depth() > 1 || !skip.contains(&e.file_name().to_string_lossy().as_ref()));
 
-    match write(path, content) {
-        Ok(_) => (),
-        Err(e) => err("Failed to create file.", Some(e.to_string()))
+    for entry in entries {
+        let entry = entry.map_err(|e| failed(e.to_string()))?;
+        let target = dest.join(entry.path().strip_prefix(src).unwrap());
+
+        if entry.file_type().is_dir() {
+            create_dir_all(&target).map_err(|e| failed(e.to_string()))?;
+        } else {
+            copy(entry.path(), &target).map_err(|e| failed(e.to_string()))?;
+        }
     }
+
+    Ok(())
+}
+
+pub fn project_path(path: Option<&str>) -> PathBuf {
+    Path::new(path.unwrap_or(".")).join(".dzp/project")
 }
 
-pub fn get_project(path: Option<&str>) -> Result<Project, ()> {
-    match read_to_string(path.unwrap_or("").to_owned() + ".dzp/project") {
+pub fn get_project(path: Option<&str>) -> Result<Project, Error> {
+    match read_to_string(project_path(path)) {
         Ok(file) => {
             match from_str::<Project>(&file) {
                 Ok(data) => Ok(data),
-                Err(e) => { err("Failed to parse project file.", Some(e.to_string())); Err(()) }
+                Err(e) => Err(Error::new("Failed to parse project file.", Some(e.to_string())))
             }
         }
-        Err(e) => { err("Failed to read project file.", Some(e.to_string())); Err(()) }
+        Err(e) => Err(Error::new("Failed to read project file.", Some(e.to_string())))
     }
-}
\ No newline at end of file
+}
+
+/// The files and directories a command has written, so they can be undone if a later step fails.
+#[derive(Debug, Default)]
+pub struct Changes {
+    /// Paths that didn't exist before, in the order they were created.
+    created: Vec<PathBuf>,
+    /// Files that were overwritten, with their previous contents.
+    replaced: Vec<(PathBuf, Vec<u8>)>
+}
+
+impl Changes {
+    /// Creates a directory and any missing parents.
+    pub fn dir(&mut self, path: &Path) -> Result<(), Error> {
+        let mut missing = path.ancestors()
+            .take_while(|p| !p.as_os_str().is_empty() && !p.exists())
+            .map(Path::to_path_buf)
+            .collect::<Vec<PathBuf>>();
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        create_dir_all(path)
+            .map_err(|e| Error::new("Failed to create directory.", Some(format!("{}: {}", path.display(), e))))?;
+
+        missing.reverse();
+        self.created.append(&mut missing);
+        Ok(())
+    }
+
+    /// Writes a file, creating its parent directories.
+    pub fn file(&mut self, path: &Path, contents: &[u8]) -> Result<(), Error> {
+        if let Some(parent) = path.parent() {
+            self.dir(parent)?;
+        }
+
+        let previous = read(path).ok();
+        write(path, contents)
+            .map_err(|e| Error::new("Failed to create file.", Some(format!("{}: {}", path.display(), e))))?;
+
+        match previous {
+            Some(previous) => self.replaced.push((path.to_path_buf(), previous)),
+            None => self.created.push(path.to_path_buf())
+        }
+
+        Ok(())
+    }
+
+    /// Records a path created outside of `Changes`, such as a git repository.
+    pub fn track(&mut self, path: &Path) {
+        self.created.push(path.to_path_buf());
+    }
+
+    /// Removes everything created and restores overwritten files, returning how many paths were undone.
+    pub fn rollback(self) -> usize {
+        let count = self.created.len() + self.replaced.len();
+
+        for (path, contents) in self.replaced {
+            let _ = write(path, contents);
+        }
+
+        for path in self.created.into_iter().rev() {
+            let _ = if path.is_dir() { remove_dir_all(&path) } else { remove_file(&path) };
+        }
+
+        count
+    }
+}
@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+
+use git2::{Commit, Index, IndexEntry, IndexTime, Repository};
+
+use crate::error::Error;
+
+/// Returns whether a style or template source is a git URL rather than a local path.
+pub fn is_remote(source: &str) -> bool {
+    source.contains("://") || source.starts_with("git@")
+}
+
+/// Clones a repository into `dest`.
+pub fn clone(url: &str, dest: &Path) -> Result<(), Error> {
+    Repository::clone(url, dest)
+        .map(|_| ())
+        .map_err(|e| Error::new("Failed to clone repository.", Some(format!("{}: {}", url, e.message()))))
+}
+
+/// Returns whether the repository has a tag with this name.
+pub fn tag_exists(repo: &Repository, tag: &str) -> bool {
+    repo.refname_to_id(&format!("refs/tags/{}", tag)).is_ok()
+}
+
+fn git_err(e: git2::Error) -> Error {
+    Error::new("Failed to commit release.", Some(e.message().to_owned()))
+}
+
+/// Commits the given files on top of `HEAD` and tags the commit with an annotated tag.
+///
+/// The commit only holds `HEAD`'s tree and these files, so anything else the user has staged stays staged.
+pub fn commit_and_tag(repo: &Repository, files: &[PathBuf], message: &str, tag: &str, tag_message: &str) -> Result<(), Error> {
+    let workdir = repo.workdir()
+        .and_then(|w| w.canonicalize().ok())
+        .ok_or_else(|| Error::new("Failed to commit release.", Some("The repository has no working directory.".to_owned())))?;
+
+    let signature = repo.signature().map_err(git_err)?;
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+
+    // Build the tree in memory, starting from HEAD rather than the user's index
+    let mut index = Index::new().map_err(git_err)?;
+
+    if let Some(parent) = &parent {
+        index.read_tree(&parent.tree().map_err(git_err)?).map_err(git_err)?;
+    }
+
+    let mut relatives = Vec::new();
+
+    for file in files {
+        let path = file.canonicalize()
+            .map_err(|e| Error::new("Failed to commit release.", Some(format!("{}: {}", file.display(), e))))?;
+        let relative = path.strip_prefix(&workdir)
+            .map_err(|_| Error::new("Failed to commit release.", Some(format!("{} is outside of the repository.", file.display()))))?
+            .to_path_buf();
+
+        let id = repo.blob_path(&path).map_err(git_err)?;
+        let size = path.metadata().map(|m| m.len() as u32).unwrap_or(0);
+        let mode = index.get_path(&relative, 0).map_or(0o100644, |e| e.mode);
+
+        index.add(&IndexEntry {
+            ctime: IndexTime::new(0, 0),
+            mtime: IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode,
+            uid: 0,
+            gid: 0,
+            file_size: size,
+            id,
+            flags: 0,
+            flags_extended: 0,
+            path: relative.to_string_lossy().replace('\\', "/").into_bytes()
+        }).map_err(git_err)?;
+
+        relatives.push(relative);
+    }
+
+    let tree = index.write_tree_to(repo)
+        .and_then(|id| repo.find_tree(id))
+        .map_err(git_err)?;
+    let parents = parent.iter().collect::<Vec<&Commit>>();
+
+    // HEAD is only moved once the tag exists, so a failure leaves the repository as it was
+    let id = repo.commit(None, &signature, &signature, message, &tree, &parents).map_err(git_err)?;
+    let commit = repo.find_object(id, None).map_err(git_err)?;
+
+    repo.tag(tag, &commit, &signature, tag_message, false).map_err(git_err)?;
+
+    let branch = repo.find_reference("HEAD").ok().and_then(|h| h.symbolic_target().map(String::from));
+    let moved = match branch {
+        Some(branch) => repo.reference(&branch, id, true, message).map(|_| ()),
+        None => repo.set_head_detached(id)
+    };
+
+    if let Err(e) = moved {
+        let _ = repo.tag_delete(tag);
+        return Err(git_err(e));
+    }
+
+    // Stage the released files, so they don't show up as changed against the new HEAD
+    let mut user_index = repo.index().map_err(git_err)?;
+
+    for relative in &relatives {
+        user_index.add_path(relative).map_err(git_err)?;
+    }
+
+    user_index.write().map_err(git_err)
+}
@@ -0,0 +1,65 @@
+//! Editing of the `#|` comment header at the top of script files.
+
+pub fn key_line(key: &str, value: &str) -> String {
+    format!("#| @{} {}", key, value)
+}
+
+fn is_key_line(line: &str, key: &str) -> bool {
+    line.strip_prefix(&format!("#| @{}", key))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+/// Returns the line range of the header, which follows any file rules.
+pub fn header_range(lines: &[String]) -> (usize, usize) {
+    let rules = lines.iter().take_while(|l| l.starts_with("#:")).count();
+    let header = lines[rules..].iter().take_while(|l| l.starts_with("#|")).count();
+
+    (rules, rules + header)
+}
+
+/// Sets a `#| @key value` line in the file's header, adding it to the end of the header if missing.
+pub fn set_key(text: &str, key: &str, value: &str) -> String {
+    let line = key_line(key, value);
+    let mut lines = text.lines().map(String::from).collect::<Vec<String>>();
+    let (start, end) = header_range(&lines);
+
+    match lines[start..end].iter().position(|l| is_key_line(l, key)) {
+        Some(i) => lines[start + i] = line,
+        None if start == end => {
+            lines.insert(start, line);
+            lines.insert(start + 1, String::new());
+        }
+        None => lines.insert(end, line)
+    }
+
+    let mut result = lines.join("\n");
+
+    if text.is_empty() || text.ends_with('\n') {
+        result.push('\n');
+    }
+
+    result
+}
+
+/// Replaces the `#| name vX` title line of the file's header, if it has one, returning whether it changed.
+pub fn set_title(text: &str, name: &str, title: &str) -> Option<String> {
+    let mut lines = text.lines().map(String::from).collect::<Vec<String>>();
+    let (start, end) = header_range(&lines);
+
+    let prefix = format!("#| {} v", name);
+    let i = lines[start..end].iter().position(|l| l.starts_with(&prefix))?;
+
+    if lines[start + i] == format!("#| {}", title) {
+        return None;
+    }
+
+    lines[start + i] = format!("#| {}", title);
+
+    let mut result = lines.join("\n");
+
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+
+    Some(result)
+}
@@ -0,0 +1,64 @@
+use ansi_term::Color::{Black, Blue, Cyan, Purple};
+
+use super::log::paint;
+
+/// Colors the tags in a piece of script, e.g. `<player.name>`.
+fn tags(text: &str) -> String {
+    let mut result = String::new();
+    let mut tag = String::new();
+    let mut depth = 0;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        // A '<' followed by a space is a comparison, not a tag
+        if c == '<' && chars.peek().is_some_and(|n| !n.is_whitespace()) {
+            depth += 1;
+        }
+
+        if depth > 0 {
+            tag.push(c);
+
+            if c == '>' {
+                depth -= 1;
+
+                if depth == 0 {
+                    result += &paint(Cyan, &tag);
+                    tag.clear();
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    // Unclosed tags are left as they are
+    result + &tag
+}
+
+/// Highlights a single line of a Denizen script for the terminal.
+pub fn highlight_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    if trimmed.starts_with('#') {
+        return format!("{}{}", indent, paint(Black.bold(), trimmed));
+    }
+
+    if let Some(command) = trimmed.strip_prefix("- ") {
+        let (name, rest) = command.split_at(command.find(' ').unwrap_or(command.len()));
+        return format!("{}- {}{}", indent, paint(Purple, name), tags(rest));
+    }
+
+    match trimmed.find(": ").or_else(|| trimmed.strip_suffix(':').map(str::len)) {
+        Some(i) => format!("{}{}{}", indent, paint(Blue, &trimmed[..i]), tags(&trimmed[i..])),
+        None => format!("{}{}", indent, tags(trimmed))
+    }
+}
+
+/// Highlights every line of a Denizen script for the terminal.
+pub fn highlight(text: &str) -> String {
+    text.lines()
+        .map(highlight_line)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
\ No newline at end of file
@@ -1,13 +1,39 @@
-use ansi_term::Color::{Red, Black};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-pub fn err(text: &str, reason: Option<String>) {
-    let msg = Red.paint(text);
+use ansi_term::{Color::{self, Red, Black, Yellow}, Style};
+
+/// Whether output is colored.
+static COLOR: AtomicBool = AtomicBool::new(true);
+
+pub fn set_color(color: bool) {
+    COLOR.store(color, Ordering::Relaxed);
+}
+
+/// Styles text for the terminal, unless color is turned off.
+pub fn paint<S: Into<Style>>(style: S, text: &str) -> String {
+    if COLOR.load(Ordering::Relaxed) {
+        style.into().paint(text).to_string()
+    } else {
+        text.to_owned()
+    }
+}
+
+fn log(color: Color, text: &str, reason: Option<String>) {
+    let msg = paint(color, text);
 
     match reason {
         Some(r) => {
-            let rsn = Black.bold().paint(r);
+            let rsn = paint(Black.bold(), &r);
             println!("{} {}", msg, rsn);
         }
         None => println!("{}", msg)
     }
-}
\ No newline at end of file
+}
+
+pub fn err(text: &str, reason: Option<String>) {
+    log(Red, text, reason)
+}
+
+pub fn warn(text: &str, reason: Option<String>) {
+    log(Yellow, text, reason)
+}
@@ -0,0 +1,94 @@
+use std::env::temp_dir;
+use std::fs::{create_dir, read, remove_dir_all};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use walkdir::WalkDir;
+
+use super::fs::copy_dir;
+use super::git::{clone, is_remote};
+use crate::error::Error;
+use crate::structs::project::Project;
+use crate::structs::style::Style;
+
+/// Entries of a template that are never copied into the project.
+const SKIP: [&str; 2] = [".git", ".dzp"];
+
+/// Creates an empty directory for a fetched template, with a name no other run uses.
+fn temp_template_dir() -> Result<PathBuf, Error> {
+    let time = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default();
+    let mut last = None;
+
+    for attempt in 0..100 {
+        let dir = temp_dir().join(format!("dzp-template-{}-{}-{}", process::id(), time, attempt));
+
+        match create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => last = Some(e),
+            Err(e) => return Err(Error::new("Failed to create temporary directory.", Some(e.to_string())))
+        }
+    }
+
+    Err(Error::new("Failed to create temporary directory.", last.map(|e| e.to_string())))
+}
+
+/// Copies a template into a temporary directory without its git history, cloning it if it's a git URL.
+///
+/// The directory is removed again if this fails, and should be passed to `clean` once the template is used.
+pub fn fetch(source: &str) -> Result<PathBuf, Error> {
+    if !is_remote(source) && !Path::new(source).is_dir() {
+        return Err(Error::new("Failed to fetch template.", Some(format!("'{}' is not a directory or git URL.", source))));
+    }
+
+    let dir = temp_template_dir()?;
+
+    let result = if is_remote(source) {
+        clone(source, &dir)
+    } else {
+        copy_dir(Path::new(source), &dir, &SKIP)
+    };
+
+    match result {
+        Ok(()) => Ok(dir),
+        Err(e) => {
+            clean(&dir);
+            Err(e)
+        }
+    }
+}
+
+/// Returns the paths and contents of a fetched template's files, with the project's variables filled in
+/// their paths and in text files.
+pub fn files(template: &Path, project: &Project) -> Result<Vec<(String, Vec<u8>)>, Error> {
+    let vars = Style::variables(project);
+    let mut result = Vec::new();
+
+    let entries = WalkDir::new(template).min_depth(1).into_iter()
+        .filter_entry(|e| e.depth() > 1 || !SKIP.contains(&e.file_name().to_string_lossy().as_ref()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file());
+
+    for entry in entries {
+        let relative = entry.path().strip_prefix(template).unwrap().to_string_lossy().replace('\\', "/");
+        let relative = Style::render(&relative, &vars);
+
+        let bytes = read(entry.path())
+            .map_err(|e| Error::new("Failed to read template file.", Some(format!("{}: {}", relative, e))))?;
+        // Binary files are copied as they are
+        let contents = match String::from_utf8(bytes) {
+            Ok(text) => Style::render(&text, &vars).into_bytes(),
+            Err(e) => e.into_bytes()
+        };
+
+        result.push((relative, contents));
+    }
+
+    Ok(result)
+}
+
+/// Removes a fetched template.
+pub fn clean(template: &Path) {
+    let _ = remove_dir_all(template);
+}
@@ -0,0 +1,176 @@
+//! Editing of top-level keys in YAML files, keeping the rest of the text and its comments as they are.
+
+use serde_yaml::{Mapping, Value};
+
+fn is_key_line(line: &str, key: &str) -> bool {
+    line.strip_prefix(key)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+/// Returns whether a line continues the value of the key above it.
+fn is_continuation(line: &str) -> bool {
+    line.starts_with(char::is_whitespace) || line.starts_with("- ") || line == "-"
+}
+
+/// Returns the line range of a top-level key and its value, leaving out trailing blank and comment lines.
+fn key_range(lines: &[String], key: &str) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|l| is_key_line(l, key))?;
+    let mut end = start + 1;
+
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        let trimmed = line.trim();
+
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            if !is_continuation(line) {
+                break;
+            }
+
+            end = i + 1;
+        }
+    }
+
+    Some((start, end))
+}
+
+/// Returns the YAML for a single key.
+pub fn key_lines(key: &str, value: &Value) -> String {
+    let mut map = Mapping::new();
+    map.insert(Value::String(key.to_owned()), value.clone());
+
+    serde_yaml::to_string(&map)
+        .unwrap_or_default()
+        .trim_start_matches("---\n")
+        .trim_end()
+        .to_owned()
+}
+
+/// Splits a line into its content and the comment at its end, if it has one.
+fn split_comment(line: &str) -> (&str, Option<&str>) {
+    let (mut single, mut double) = (false, false);
+    let mut previous = ' ';
+
+    for (i, c) in line.char_indices() {
+        match c {
+            '\'' if !double => single = !single,
+            '"' if !single && previous != '\\' => double = !double,
+            '#' if !single && !double && previous.is_whitespace() => {
+                let content = line[..i].trim_end();
+                return (content, Some(&line[content.len()..]));
+            }
+            _ => ()
+        }
+
+        previous = c;
+    }
+
+    (line.trim_end(), None)
+}
+
+/// Carries the comments of a key's old lines over to its new lines.
+///
+/// A comment at the end of a line is kept if the new value has the same line, such as an unchanged sequence item.
+/// Comment lines stay above the line they were above, or move to the end of the value if that line is gone.
+fn keep_comments(old: &[String], new: Vec<String>) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut above = Vec::new();
+
+    for line in old {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            above.push(line.clone());
+        } else {
+            let (content, comment) = split_comment(line);
+            entries.push((content, comment, std::mem::take(&mut above), false));
+        }
+    }
+
+    let mut lines = Vec::new();
+
+    for line in new {
+        match entries.iter_mut().find(|(content, _, _, used)| !*used && *content == line) {
+            Some((_, comment, above, used)) => {
+                *used = true;
+                lines.append(above);
+                lines.push(line + comment.unwrap_or_default());
+            }
+            None => lines.push(line)
+        }
+    }
+
+    for (_, _, above, _) in entries.iter_mut() {
+        lines.append(above);
+    }
+
+    lines
+}
+
+/// Sets a top-level key, replacing its current value or adding it to the end of the file.
+pub fn set_key(text: &str, key: &str, value: &Value) -> String {
+    let mut lines = text.lines().map(String::from).collect::<Vec<String>>();
+    let new = key_lines(key, value).lines().map(String::from).collect::<Vec<String>>();
+
+    match key_range(&lines, key) {
+        Some((start, end)) => {
+            let new = keep_comments(&lines[start..end], new);
+            let _ = lines.splice(start..end, new);
+        }
+        None => lines.extend(new)
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::Value;
+
+    use super::set_key;
+
+    fn authors(names: &[&str]) -> Value {
+        Value::Sequence(names.iter().map(|n| Value::String(n.to_string())).collect())
+    }
+
+    #[test]
+    fn replaces_a_value_in_place() {
+        let text = "# Project\nname: a\nversion: 1.0.0 # current\nlicense: MIT\n";
+        let expected = "# Project\nname: a\nversion: 1.1.0\nlicense: MIT\n";
+
+        assert_eq!(set_key(text, "version", &Value::String("1.1.0".to_owned())), expected);
+    }
+
+    #[test]
+    fn adds_a_missing_key_to_the_end() {
+        let text = "name: a\n\n# The end\n";
+        let expected = "name: a\n\n# The end\nversion: 1.0.0\n";
+
+        assert_eq!(set_key(text, "version", &Value::String("1.0.0".to_owned())), expected);
+    }
+
+    #[test]
+    fn keeps_comments_of_unchanged_items() {
+        let text = "authors: # Everyone\n  - a   # first\n  # Second\n  - b\nversion: 1.0.0\n";
+        let expected = "authors: # Everyone\n  - a   # first\n  # Second\n  - b\n  - c\nversion: 1.0.0\n";
+
+        assert_eq!(set_key(text, "authors", &authors(&["a", "b", "c"])), expected);
+    }
+
+    #[test]
+    fn keeps_comments_of_removed_items() {
+        let text = "authors:\n  - a\n  # Second\n  - b # gone\n";
+        let expected = "authors:\n  - a\n  # Second\n";
+
+        assert_eq!(set_key(text, "authors", &authors(&["a"])), expected);
+    }
+
+    #[test]
+    fn ignores_hashes_in_quotes() {
+        let text = "authors:\n  - \"a #1\"\n";
+        let expected = "authors:\n  - \"a #1\"\n  - b\n";
+
+        assert_eq!(set_key(text, "authors", &authors(&["a #1", "b"])), expected);
+    }
+}
@@ -0,0 +1,15 @@
+//! DeniZip, the Denizen Project Manager.
+//!
+//! The `dzp` command-line tool is built on top of this crate, which can be used
+//! to load projects, enumerate their scripts, read kDoc and compile scripts.
+
+pub mod analysis;
+pub mod cache;
+pub mod config;
+pub mod docgen;
+pub mod error;
+pub mod io;
+pub mod readme;
+pub mod structs;
+
+pub use error::Error;
@@ -1,9 +1,7 @@
 use clap::{App, load_yaml};
 
-mod cache;
-mod config;
-mod io;
-mod structs;
+use dzp::{config, io};
+
 mod subcmd;
 
 fn main() {
@@ -13,14 +11,27 @@ fn main() {
     let yaml = load_yaml!("cli.yml");
     let mut app = App::from_yaml(yaml);
 
-    config::styles::init_styles(true);
+    if let Err(e) = config::styles::init_styles() {
+        e.log();
+    }
+
+    match config::user::read_config() {
+        Ok(user) => io::log::set_color(user.color()),
+        Err(e) => e.log()
+    }
 
-    let _ = match app.clone().get_matches().subcommand() {
-        ("compile", Some(sub)) => subcmd::compile::compile(&sub),
-        ("new", Some(sub)) => subcmd::new::new(&sub),
-        ("project", Some(sub)) => subcmd::project::project(&sub),
-        ("readme", _) => subcmd::readme::readme(),
-        ("script", Some(sub)) => subcmd::script::script(&sub),
+    match app.clone().get_matches().subcommand() {
+        ("bump", Some(sub)) => subcmd::bump::bump(sub),
+        ("compile", Some(sub)) => subcmd::compile::compile(sub),
+        ("doc", Some(sub)) => subcmd::doc::doc(sub),
+        ("init", Some(sub)) => subcmd::init::init(sub),
+        ("license", Some(sub)) => subcmd::license::license(sub),
+        ("new", Some(sub)) => subcmd::new::new(sub),
+        ("project", Some(sub)) => subcmd::project::project(sub),
+        ("readme", Some(sub)) => subcmd::readme::readme(sub),
+        ("script", Some(sub)) => subcmd::script::script(sub),
+        ("search", Some(sub)) => subcmd::search::search(sub),
+        ("style", Some(sub)) => subcmd::style::style(sub),
         _ => { let _ = app.print_help(); }
     };
 }
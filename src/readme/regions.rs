@@ -0,0 +1,213 @@
+//! dzp-managed regions of a README, delimited by `<!-- dzp:name -->` and `<!-- /dzp:name -->`.
+//!
+//! The opening marker can also hold options after the name, such as `<!-- dzp:scripts detail=full -->`.
+
+const OPEN: &str = "<!-- dzp:";
+const CLOSE: &str = "-->";
+
+/// A region of a README, without its markers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub name: String,
+    /// The options after the name in the opening marker, separated by spaces.
+    pub options: String,
+    pub content: String
+}
+
+impl Region {
+    pub fn new(name: &str, options: &str, content: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            options: options.to_owned(),
+            content: content.to_owned()
+        }
+    }
+
+    /// Returns the value of a `key=value` option.
+    pub fn option(&self, key: &str) -> Option<&str> {
+        self.options.split_whitespace()
+            .find_map(|o| o.strip_prefix(key).and_then(|rest| rest.strip_prefix('=')))
+    }
+
+    /// Returns whether the region would be written the same as another.
+    fn matches(&self, other: &Region) -> bool {
+        self.options == other.options && self.content.trim_matches('\n') == other.content.trim_matches('\n')
+    }
+
+    /// Returns the region along with its markers.
+    pub fn wrap(&self) -> String {
+        wrap(&self.name, &self.options, &self.content)
+    }
+}
+
+pub fn start(name: &str, options: &str) -> String {
+    if options.is_empty() {
+        format!("{}{} {}", OPEN, name, CLOSE)
+    } else {
+        format!("{}{} {} {}", OPEN, name, options, CLOSE)
+    }
+}
+
+pub fn end(name: &str) -> String {
+    format!("<!-- /dzp:{} -->", name)
+}
+
+pub fn wrap(name: &str, options: &str, content: &str) -> String {
+    format!("{}\n{}\n{}", start(name, options), content.trim_matches('\n'), end(name))
+}
+
+/// Returns every region in `text` in order, along with its byte range including the markers.
+///
+/// An opening marker without a closing marker after it is skipped, as is anything inside a region.
+fn parse(text: &str) -> Vec<(usize, usize, Region)> {
+    let mut result = Vec::new();
+    let mut from = 0;
+
+    while let Some(found) = text[from..].find(OPEN) {
+        let begin = from + found;
+        let inner = begin + OPEN.len();
+        from = inner;
+
+        // The opening marker has to be on one line
+        let marker = match text[inner..].find(CLOSE) {
+            Some(i) if !text[inner..inner + i].contains('\n') => &text[inner..inner + i],
+            _ => continue
+        };
+
+        let mut words = marker.trim().splitn(2, char::is_whitespace);
+        let name = words.next().unwrap_or_default();
+        let options = words.next().unwrap_or_default().trim();
+
+        let content_begin = inner + marker.len() + CLOSE.len();
+        let close = end(name);
+
+        if let Some(i) = text[content_begin..].find(&close) {
+            let finish = content_begin + i + close.len();
+
+            result.push((begin, finish, Region::new(name, options, &text[content_begin..content_begin + i])));
+            from = finish;
+        }
+    }
+
+    result
+}
+
+/// Returns the first region with this name in `text`.
+pub fn find(text: &str, name: &str) -> Option<Region> {
+    parse(text).into_iter()
+        .map(|(_, _, region)| region)
+        .find(|r| r.name == name)
+}
+
+/// Returns every region in `text`, in order.
+pub fn all(text: &str) -> Vec<Region> {
+    parse(text).into_iter().map(|(_, _, region)| region).collect()
+}
+
+pub fn contains_any(text: &str, regions: &[Region]) -> bool {
+    parse(text).iter().any(|(_, _, current)| regions.iter().any(|r| r.name == current.name))
+}
+
+/// Replaces the regions present in `text` with new content, keeping everything around them as it is.
+///
+/// Returns the updated text along with the names of the regions that changed.
+/// Regions that aren't present in `text` are skipped, and a region present more than once is replaced everywhere.
+pub fn update(text: &str, regions: &[Region]) -> (String, Vec<String>) {
+    let mut result = String::new();
+    let mut stale = Vec::new();
+    let mut last = 0;
+
+    for (begin, finish, current) in parse(text) {
+        let new = match regions.iter().find(|r| r.name == current.name) {
+            Some(new) if !new.matches(&current) => new,
+            _ => continue
+        };
+
+        result.push_str(&text[last..begin]);
+        result.push_str(&new.wrap());
+        last = finish;
+
+        if !stale.contains(&new.name) {
+            stale.push(new.name.clone());
+        }
+    }
+
+    result.push_str(&text[last..]);
+    (result, stale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{all, find, update, wrap, Region};
+
+    fn scripts(content: &str) -> Vec<Region> {
+        vec![Region::new("scripts", "", content)]
+    }
+
+    #[test]
+    fn reads_regions_and_options() {
+        let text = "# A\n<!-- dzp:setup -->\nclone\n<!-- /dzp:setup -->\n<!-- dzp:scripts detail=full -->\n- a\n<!-- /dzp:scripts -->\n";
+        let regions = all(text);
+
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0], Region::new("setup", "", "\nclone\n"));
+        assert_eq!(regions[1].option("detail"), Some("full"));
+        assert_eq!(find(text, "scripts").and_then(|r| r.option("size").map(String::from)), None);
+    }
+
+    #[test]
+    fn keeps_text_outside_regions() {
+        let text = "# A  \n\r\nIntro\t\n<!-- dzp:scripts -->\nold\n<!-- /dzp:scripts -->\n\n  Outro <!-- note -->";
+        let (updated, stale) = update(text, &scripts("new"));
+
+        assert_eq!(updated, "# A  \n\r\nIntro\t\n<!-- dzp:scripts -->\nnew\n<!-- /dzp:scripts -->\n\n  Outro <!-- note -->");
+        assert_eq!(stale, ["scripts"]);
+    }
+
+    #[test]
+    fn leaves_unchanged_regions_alone() {
+        let text = "<!-- dzp:scripts -->\n\nsame\n\n<!-- /dzp:scripts -->\n";
+        let (updated, stale) = update(text, &scripts("same"));
+
+        assert_eq!(updated, text);
+        assert!(stale.is_empty());
+    }
+
+    #[test]
+    fn skips_regions_without_a_close_marker() {
+        let text = "<!-- dzp:scripts -->\nold\n<!-- dzp:license -->\nMIT\n<!-- /dzp:license -->\n";
+        let regions = vec![Region::new("scripts", "", "new"), Region::new("license", "", "GPL")];
+        let (updated, stale) = update(text, &regions);
+
+        assert_eq!(updated, "<!-- dzp:scripts -->\nold\n<!-- dzp:license -->\nGPL\n<!-- /dzp:license -->\n");
+        assert_eq!(stale, ["license"]);
+    }
+
+    #[test]
+    fn updates_every_copy_of_a_region() {
+        let text = format!("{}\ntext\n{}\n", wrap("scripts", "", "old"), wrap("scripts", "", "older"));
+        let (updated, stale) = update(&text, &scripts("new"));
+
+        assert_eq!(updated, format!("{}\ntext\n{}\n", wrap("scripts", "", "new"), wrap("scripts", "", "new")));
+        assert_eq!(stale, ["scripts"]);
+    }
+
+    #[test]
+    fn skips_added_and_removed_regions() {
+        let text = format!("{}\n{}\n", wrap("custom", "", "mine"), wrap("scripts", "", "old"));
+        let regions = vec![Region::new("setup", "", "clone"), Region::new("scripts", "", "new")];
+        let (updated, stale) = update(&text, &regions);
+
+        assert_eq!(updated, format!("{}\n{}\n", wrap("custom", "", "mine"), wrap("scripts", "", "new")));
+        assert_eq!(stale, ["scripts"]);
+    }
+
+    #[test]
+    fn rewrites_changed_options() {
+        let text = wrap("scripts", "detail=full", "- a");
+        let (updated, stale) = update(&text, &scripts("- a"));
+
+        assert_eq!(updated, wrap("scripts", "", "- a"));
+        assert_eq!(stale, ["scripts"]);
+    }
+}
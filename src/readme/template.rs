@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use minijinja::{context, Environment};
+use serde::Serialize;
+
+use crate::cache::scripts::Scripts;
+use crate::config::user::UserConfig;
+use crate::error::Error;
+use crate::structs::project::{Detail, Project};
+use crate::structs::script::Script;
+
+#[derive(Serialize)]
+struct ScriptContext<'a> {
+    name: &'a str,
+    #[serde(flatten)]
+    script: &'a Script,
+    deprecated: bool,
+    private: bool
+}
+
+impl<'a> ScriptContext<'a> {
+    fn new(name: &'a str, script: &'a Script) -> Self {
+        Self {
+            name,
+            script,
+            deprecated: script.is_deprecated(),
+            private: script.is_private()
+        }
+    }
+}
+
+/// Renders a README template written in Jinja syntax.
+///
+/// Templates have access to `project`, `title`, `scripts` (a list of every script),
+/// `types` (scripts grouped by type) and `sections` (the sections of the default README).
+pub fn render(template: &str, project: &Project, scripts: &Scripts, detail: Detail, config: &UserConfig) -> Result<String, Error> {
+    let mut env = Environment::new();
+    env.set_keep_trailing_newline(true);
+
+    if let Err(e) = env.add_template("readme", template) {
+        return Err(Error::new("Failed to parse README template.", Some(e.to_string())));
+    }
+
+    let list = scripts.iter()
+        .map(|(name, script)| ScriptContext::new(name, script))
+        .collect::<Vec<ScriptContext>>();
+
+    let mut types = BTreeMap::<&str, Vec<ScriptContext>>::new();
+
+    for (name, script) in scripts {
+        types.entry(script.script_type.as_str()).or_default().push(ScriptContext::new(name, script));
+    }
+
+    let sections = project.readme_sections(scripts, detail, config);
+
+    let ctx = context! {
+        project => project,
+        title => project.title(),
+        scripts => list,
+        types => types,
+        sections => sections
+    };
+
+    env.get_template("readme")
+        .and_then(|t| t.render(ctx))
+        .map_err(|e| Error::new("Failed to render README template.", Some(e.to_string())))
+}
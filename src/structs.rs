@@ -1,3 +1,6 @@
+pub mod command;
 pub mod doc;
 pub mod project;
-pub mod script;
\ No newline at end of file
+pub mod script;
+pub mod style;
+pub mod tag_type;
\ No newline at end of file
@@ -0,0 +1,69 @@
+use serde::{Serialize, Deserialize};
+use serde_yaml::Value;
+
+use crate::cache::scripts::ScriptContents;
+
+/// The metadata of a command script.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Command {
+    pub name: String,
+    pub description: Option<String>,
+    pub usage: Option<String>,
+    pub aliases: Vec<String>,
+    pub permission: Option<String>
+}
+
+fn text(contents: &ScriptContents, key: &str) -> Option<String> {
+    match contents.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None
+    }
+}
+
+impl Command {
+    pub fn from(contents: &ScriptContents) -> Option<Self> {
+        let aliases = match contents.get("aliases") {
+            Some(Value::Sequence(seq)) => seq.iter().filter_map(|a| a.as_str()).map(String::from).collect(),
+            Some(Value::String(s)) => vec![s.clone()],
+            _ => Vec::new()
+        };
+
+        Some(Self {
+            name: text(contents, "name")?,
+            description: text(contents, "description"),
+            usage: text(contents, "usage"),
+            aliases,
+            permission: text(contents, "permission")
+        })
+    }
+
+    /// Returns the command name and its aliases, each with a leading slash.
+    pub fn labels(&self) -> Vec<String> {
+        std::iter::once(&self.name)
+            .chain(self.aliases.iter())
+            .map(|n| format!("/{}", n))
+            .collect()
+    }
+
+    pub fn print(&self) {
+        println!("Command: /{}", self.name);
+
+        if let Some(description) = &self.description {
+            println!("Command Description: {}", description);
+        }
+
+        if !self.aliases.is_empty() {
+            println!("Aliases: {}", self.labels()[1..].join(", "));
+        }
+
+        if let Some(usage) = &self.usage {
+            println!("Command Usage: {}", usage);
+        }
+
+        if let Some(permission) = &self.permission {
+            println!("Permission: {}", permission);
+        }
+    }
+}
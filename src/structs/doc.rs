@@ -1,12 +1,15 @@
 use std::collections::BTreeMap;
 
-use lazer::lazer;
-use serde::{Serialize, Deserialize};
-use serde_yaml::from_value;
+use ansi_term::Color::Yellow;
+use semver::Version;
+use serde::{Serialize, Deserialize, Deserializer};
+use serde_yaml::{from_value, Value};
 
+use super::tag_type::TagType;
 use crate::cache::scripts::ScriptContents;
+use crate::io::log::paint;
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocArg {
     #[serde(alias = "desc")]
     #[serde(alias = "about")]
@@ -16,6 +19,17 @@ pub struct DocArg {
 }
 
 impl DocArg {
+    /// Every argument key along with its aliases.
+    pub const KEYS: [(&'static str, &'static [&'static str]); 2] = [
+        ("description", &["desc", "about"]),
+        ("type", &[])
+    ];
+
+    /// Parses the argument's type, if it has one.
+    pub fn tag_type(&self) -> Option<Result<TagType, String>> {
+        self.arg_type.as_deref().map(str::parse)
+    }
+
     pub fn display(arg: Option<Self>, name: Option<String>, sep: &str) -> Option<String> {
         if let Some(arg) = arg {
             let t = match arg.arg_type {
@@ -36,13 +50,111 @@ impl DocArg {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+/// A deprecation notice, either a plain flag or a reason with a replacement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Deprecation {
+    Flag(bool),
+    Notice {
+        reason: Option<String>,
+        replacement: Option<String>
+    }
+}
+
+impl Deprecation {
+    /// Every deprecation notice key.
+    pub const KEYS: [(&'static str, &'static [&'static str]); 2] = [
+        ("reason", &[]),
+        ("replacement", &[])
+    ];
+
+    pub fn is_deprecated(&self) -> bool {
+        match self {
+            Self::Flag(b) => *b,
+            Self::Notice { .. } => true
+        }
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Notice { reason, .. } => reason.as_deref(),
+            _ => None
+        }
+    }
+
+    pub fn replacement(&self) -> Option<&str> {
+        match self {
+            Self::Notice { replacement, .. } => replacement.as_deref(),
+            _ => None
+        }
+    }
+
+    /// Describes the reason and replacement, if any, in a sentence.
+    pub fn message(&self) -> Option<String> {
+        let replacement = self.replacement().map(|r| format!("Use '{}' instead.", r));
+
+        match (self.reason(), replacement) {
+            (Some(r), Some(u)) => Some(format!("{} {}", r, u)),
+            (Some(r), None) => Some(r.to_owned()),
+            (None, u) => u
+        }
+    }
+}
+
+/// A usage example, either plain code or code with a caption.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Example {
+    Code(String),
+    Captioned {
+        #[serde(alias = "title")]
+        caption: Option<String>,
+        code: String
+    }
+}
+
+impl Example {
+    /// Every captioned example key along with its aliases.
+    pub const KEYS: [(&'static str, &'static [&'static str]); 2] = [
+        ("caption", &["title"]),
+        ("code", &[])
+    ];
+
+    pub fn caption(&self) -> Option<&str> {
+        match self {
+            Self::Captioned { caption, .. } => caption.as_deref(),
+            _ => None
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Code(code) | Self::Captioned { code, .. } => code
+        }
+    }
+}
+
+/// Reads a version leniently, so an invalid `since` is left out instead of failing the whole doc.
+fn lenient_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Version>, D::Error> {
+    Ok(Value::deserialize(deserializer).ok().as_ref().and_then(Doc::coerce_version))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Doc {
-    pub deprecated: Option<bool>,
+    pub deprecated: Option<Deprecation>,
+    pub private: Option<bool>,
     #[serde(alias = "desc")]
     #[serde(alias = "about")]
     pub description: Option<String>,
     pub usage: Option<String>,
+    #[serde(alias = "example")]
+    pub examples: Option<Vec<Example>>,
+    #[serde(default, deserialize_with = "lenient_version")]
+    pub since: Option<Version>,
+    pub author: Option<String>,
+    #[serde(alias = "categories")]
+    pub tags: Option<Vec<String>>,
+    pub see: Option<Vec<String>>,
     #[serde(alias = "determines")]
     pub determine: Option<DocArg>,
     #[serde(alias = "use")]
@@ -53,20 +165,72 @@ pub struct Doc {
 }
 
 impl Doc {
-    pub fn from(contents: &ScriptContents) -> Option<Self> {
-        let data = match contents.get("data") {
-            Some(d) => d,
-            None => contents.get("doc")?
+    /// Reads a `since` value as a version, accepting numbers and short versions such as `1.2`.
+    pub fn coerce_version(value: &Value) -> Option<Version> {
+        let text = match value {
+            Value::String(s) => s.trim().trim_start_matches('v').to_owned(),
+            Value::Number(n) => n.to_string(),
+            _ => return None
+        };
+
+        let parts = text.split('.').count();
+        let padded = if parts < 3 && text.split('.').all(|p| p.parse::<u64>().is_ok()) {
+            format!("{}{}", text, ".0".repeat(3 - parts))
+        } else {
+            text
         };
-        
+
+        Version::parse(&padded).ok()
+    }
+
+    /// Every kDoc key along with its aliases.
+    pub const KEYS: [(&'static str, &'static [&'static str]); 13] = [
+        ("deprecated", &[]),
+        ("private", &[]),
+        ("description", &["desc", "about"]),
+        ("usage", &[]),
+        ("examples", &["example"]),
+        ("since", &[]),
+        ("author", &[]),
+        ("tags", &["categories"]),
+        ("see", &[]),
+        ("determine", &["determines"]),
+        ("uses", &["use"]),
+        ("defs", &["definitions"]),
+        ("keys", &[])
+    ];
+
+    /// Returns the main name of a kDoc key, resolving aliases.
+    pub fn canonical_key(keys: &[(&'static str, &'static [&'static str])], key: &str) -> Option<&'static str> {
+        keys.iter()
+            .find(|(name, aliases)| *name == key || aliases.contains(&key))
+            .map(|(name, _)| *name)
+    }
+
+    /// Returns the script key that holds the kDoc.
+    ///
+    /// Data scripts prefer the `doc` key, since their `data` key may hold actual data.
+    pub fn source_key(contents: &ScriptContents) -> Option<&'static str> {
+        let data_script = contents.get("type").and_then(|t| t.as_str()) == Some("data");
+        let order = if data_script { ["doc", "data"] } else { ["data", "doc"] };
+
+        order.iter().copied().find(|k| contents.contains_key(*k))
+    }
+
+    pub fn from(contents: &ScriptContents) -> Option<Self> {
+        let data = contents.get(Self::source_key(contents)?)?;
+
         from_value::<Self>(data.clone()).ok()
     }
 
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated.as_ref().is_some_and(Deprecation::is_deprecated)
+    }
+
     fn print_optional_str(key: &str, value: Option<String>) {
-        lazer()
-            .iff(value.is_some())
-                .print_ln(&format!("{}{}", key, value.unwrap_or(String::new())))
-            .end();
+        if let Some(value) = value {
+            println!("{}{}", key, value);
+        }
     }
 
     fn print_arg_list(key: &str, value: &Option<BTreeMap<String, DocArg>>) {
@@ -75,27 +239,51 @@ impl Doc {
             .map(|s| format!("- {}", s))
             .collect::<Vec<String>>();
 
-        lazer()
-            .iff(value.is_some())
-                .print_ln(&format!("\n{}:\n{}", key, result.join("\n")));
+        if value.is_some() {
+            println!("\n{}:\n{}", key, result.join("\n"));
+        }
+    }
+
+    fn print_examples(&self) {
+        if let Some(examples) = &self.examples {
+            println!("\nExamples:");
+
+            for example in examples {
+                if let Some(caption) = example.caption() {
+                    println!("# {}", caption);
+                }
+
+                println!("{}", example.code().trim_end());
+            }
+        }
     }
 
     pub fn print(&self) {
-        lazer()
-            .iff(self.deprecated.unwrap_or(false))
-                .print_yellow_ln("WARNING: This script is deprecated!\n");
+        let notice = self.deprecated.as_ref()
+            .and_then(Deprecation::message)
+            .map(|m| format!(" {}", m))
+            .unwrap_or_default();
+
+        if self.is_deprecated() {
+            println!("{}", paint(Yellow, &format!("WARNING: This script is deprecated!{}\n", notice)));
+        }
 
         Self::print_optional_str("Description: ", self.description.clone());
+        Self::print_optional_str("Since: ", self.since.as_ref().map(Version::to_string));
+        Self::print_optional_str("Author: ", self.author.clone());
+        Self::print_optional_str("Tags: ", self.tags.as_ref().map(|t| t.join(", ")));
 
-        lazer()
-            .iff(self.determine.is_some())
-                .print_ln(&format!("Determine: {}", DocArg::display(self.determine.clone(), None, " -").unwrap_or_default()))
-            .end()
-            .iff(self.uses.is_some())
-                .print_ln(&format!("Uses: {}", self.uses.clone().unwrap_or(Vec::new()).join(", ")))
-            .end();
+        if self.determine.is_some() {
+            println!("Determine: {}", DocArg::display(self.determine.clone(), None, " -").unwrap_or_default());
+        }
+
+        if let Some(uses) = &self.uses {
+            println!("Uses: {}", uses.join(", "));
+        }
 
+        Self::print_optional_str("See: ", self.see.as_ref().map(|s| s.join(", ")));
         Self::print_optional_str("\nUsage:\n", self.usage.clone());
+        self.print_examples();
         Self::print_arg_list("Defs", &self.defs);
         Self::print_arg_list("Data Keys:", &self.keys)
     }
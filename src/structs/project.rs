@@ -1,122 +1,227 @@
-use lazer::lazer;
-use license::from_id;
-use rustyline::Editor;
-use semver::Version;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use license::{from_id, from_id_exception};
+use semver::{Prerelease, Version};
 use serde::{Serialize, Deserialize};
-use serde_yaml::to_string;
+use serde_yaml::{to_string, Value};
+use spdx::{Expression, LicenseItem, ParseMode};
 use titlecase::titlecase;
 use url::Url;
 
-use crate::cache::scripts::get_script_types;
-use crate::io::log::err;
+use crate::cache::scripts::{script_types, Scripts};
+use crate::config::user::UserConfig;
+use crate::docgen::markdown::{self, Links};
+use crate::error::Error;
+use crate::io::date::year;
+use crate::readme::regions;
+use crate::structs::script::Script;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
     pub name: String,
+    pub namespace: Option<String>,
     pub description: String,
     pub authors: Vec<String>,
     pub version: Version,
     pub license: String,
+    pub license_file: Option<String>,
     pub homepage: Option<Url>,
     pub repository: Option<Url>,
-    pub dependencies: Option<Vec<String>>
+    pub copyright: Option<String>,
+    pub dependencies: Option<Vec<String>>,
+    /// Directories holding the project's scripts. The whole project is searched if unset.
+    pub sources: Option<Vec<String>>
 }
 
-type Error = rustyline::error::ReadlineError;
+/// Project details given up front, such as from command-line flags, instead of being asked for.
+#[derive(Debug, Clone, Default)]
+pub struct Preset {
+    pub namespace: Option<String>,
+    pub description: Option<String>,
+    pub authors: Option<Vec<String>>,
+    pub version: Option<Version>,
+    pub license: Option<String>,
+    pub homepage: Option<Url>,
+    pub repository: Option<Url>
+}
 
-impl Project {
-    fn input(text: &str, rl: &mut Editor<()>) -> Result<String, Error> {
-        let line = rl.readline(&format!("{}: ", text))?;
-        rl.add_history_entry(&line);
-        Ok(line.trim().to_string())
-    }
+/// How much of each script's kDoc the generated README includes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detail {
+    /// Script names only.
+    Names,
+    /// Script names and descriptions.
+    Summary,
+    /// Descriptions, usage, determine and definitions.
+    Full
+}
 
-    fn input_authors(rl: &mut Editor<()>) -> Result<Vec<String>, Error> {
-        let input = Self::input("Authors", rl)?;
+impl FromStr for Detail {
+    type Err = String;
 
-        Ok(input
-            .split_terminator(',')
-            .map(|s| String::from(s.trim()))
-            .filter(|s| !s.is_empty())
-            .collect())
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "names" => Ok(Self::Names),
+            "summary" => Ok(Self::Summary),
+            "full" => Ok(Self::Full),
+            _ => Err(format!("Unknown detail level '{}'.", s))
+        }
     }
+}
 
-    fn input_version(rl: &mut Editor<()>) -> Result<Version, Error> {
-        loop {
-            let input = Self::input("Version", rl)?;
-            if input.is_empty() { return Ok(Version::new(0, 1, 0)) }
+impl fmt::Display for Detail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Names => write!(f, "names"),
+            Self::Summary => write!(f, "summary"),
+            Self::Full => write!(f, "full")
+        }
+    }
+}
 
-            let v = Version::parse(&input);
-    
-            match v {
-                Ok(version) => return Ok(version),
-                Err(_) => err("Invalid version.", None)
-            }
+/// Which part of a version `dzp bump` increments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+    /// A pre-release with the given identifier, such as `beta` for `1.0.1-beta.0`.
+    Prerelease(String)
+}
+
+impl Bump {
+    pub fn from_part(part: &str, id: Option<&str>) -> Result<Self, Error> {
+        let invalid = |reason: String| Error::new("Invalid version part.", Some(reason));
+
+        match (part, id) {
+            ("major", _) => Ok(Self::Major),
+            ("minor", _) => Ok(Self::Minor),
+            ("patch", _) => Ok(Self::Patch),
+            ("prerelease", Some(id)) => Ok(Self::Prerelease(id.to_owned())),
+            ("prerelease", None) => Err(invalid("A pre-release needs an identifier, e.g. 'beta'.".to_owned())),
+            _ => Err(invalid(format!("Unknown version part '{}'.", part)))
         }
     }
-    
-    fn input_url(text: &str, rl: &mut Editor<()>) -> Result<Option<Url>, Error> {
-        loop {
-            let input = Self::input(text, rl)?;
-            if input.is_empty() { return Ok(None) }
 
-            let u = Url::parse(&input);
-    
-            match u {
-                Ok(url) => return Ok(Some(url)),
-                Err(_) => err("Invalid URL.", None)
+    /// Returns the next version. A pre-release is released by the part it leads up to, e.g.
+    /// `1.1.0-beta.2` becomes `1.1.0` with `minor`.
+    pub fn apply(&self, version: &Version) -> Result<Version, Error> {
+        let (major, minor, patch) = (version.major, version.minor, version.patch);
+        let pre = !version.pre.is_empty();
+
+        let next = match self {
+            Self::Major if pre && minor == 0 && patch == 0 => Version::new(major, 0, 0),
+            Self::Major => Version::new(major + 1, 0, 0),
+            Self::Minor if pre && patch == 0 => Version::new(major, minor, 0),
+            Self::Minor => Version::new(major, minor + 1, 0),
+            Self::Patch if pre => Version::new(major, minor, patch),
+            Self::Patch => Version::new(major, minor, patch + 1),
+            Self::Prerelease(id) => {
+                // Continues a pre-release with the same identifier, otherwise starts one for the next patch
+                let count = version.pre.as_str()
+                    .strip_prefix(id.as_str())
+                    .and_then(|rest| rest.strip_prefix('.'))
+                    .and_then(|n| n.parse::<u64>().ok());
+
+                let base = if pre { Version::new(major, minor, patch) } else { Version::new(major, minor, patch + 1) };
+                let pre = format!("{}.{}", id, count.map_or(0, |n| n + 1));
+
+                let pre = Prerelease::new(&pre).map_err(|e| Error::new("Invalid version.", Some(format!("{}: {}", pre, e))))?;
+                Version { pre, ..base }
             }
-        }
+        };
+
+        Ok(next)
     }
+}
 
-    fn input_license(rl: &mut Editor<()>) -> Result<String, Error> {
-        loop {
-            let input = Self::input("License", rl)?;
-            if input.is_empty() { return Ok(String::from("MIT")) }
+impl Project {
+    /// Creates a project from the preset without asking for anything, using defaults for the rest.
+    pub fn from_preset(name: String, preset: &Preset) -> Self {
+        let project = Self::from_name(name);
+        let preset = preset.clone();
 
-            let l = from_id(&input);
+        Self {
+            namespace: preset.namespace,
+            description: preset.description.unwrap_or(project.description),
+            authors: preset.authors.unwrap_or(project.authors),
+            version: preset.version.unwrap_or(project.version),
+            license: preset.license.unwrap_or(project.license),
+            homepage: preset.homepage,
+            repository: preset.repository,
+            ..project
+        }
+    }
 
-            match l {
-                Some(_) => return Ok(String::from(input)),
-                None => err("Invalid license.", None)
-            }
+    /// Returns the project's details, such as to suggest them when editing it.
+    pub fn preset(&self) -> Preset {
+        Preset {
+            namespace: self.namespace.clone(),
+            description: Some(self.description.clone()),
+            authors: Some(self.authors.clone()),
+            version: Some(self.version.clone()),
+            license: Some(self.license.clone()),
+            homepage: self.homepage.clone(),
+            repository: self.repository.clone()
         }
     }
 
-    pub fn from_input(name: String) -> Result<Self, Error> {
-        let mut rl = Editor::<()>::new();
+    /// Fields of the project file that can be set from the command line.
+    pub const FIELDS: [&'static str; 10] = [
+        "name", "namespace", "description", "authors", "version", "license", "license_file", "homepage", "repository", "copyright"
+    ];
 
-        let description: String = Self::input("Description", &mut rl)?;
-        let authors = Self::input_authors(&mut rl)?;
-        let version = Self::input_version(&mut rl)?;
-        let license = Self::input_license(&mut rl)?;
-        let homepage = Self::input_url("Homepage", &mut rl)?;
-        let repository = Self::input_url("Repository", &mut rl)?;
+    /// Validates a value for a project field, returning it as it's written to the project file.
+    ///
+    /// An empty value unsets optional fields. Authors are separated by commas.
+    pub fn field_value(field: &str, value: &str) -> Result<Value, Error> {
+        let value = value.trim();
+        let required = ["name", "description", "authors", "version", "license"].contains(&field);
 
-        println!();
+        if value.is_empty() && !required {
+            return Ok(Value::Null);
+        }
 
-        Ok(Self {
-            name,
-            description,
-            authors,
-            version,
-            license,
-            homepage,
-            repository,
-            dependencies: Some(Vec::new())
-        })
+        let result = match field {
+            "name" if value.is_empty() => Err("The name can't be empty.".to_owned()),
+            "authors" => Ok(Value::Sequence(value
+                .split_terminator(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| Value::String(s.to_owned()))
+                .collect())),
+            "version" => Version::parse(value)
+                .map(|v| Value::String(v.to_string()))
+                .map_err(|e| format!("Invalid version: {}", e)),
+            "license" => Self::parse_license(value)
+                .map(|_| Value::String(value.to_owned()))
+                .map_err(|e| format!("Invalid license: {}", e.reason.unwrap_or_default())),
+            "homepage" | "repository" => Url::parse(value)
+                .map(|u| Value::String(u.to_string()))
+                .map_err(|e| format!("Invalid URL: {}", e)),
+            f if Self::FIELDS.contains(&f) => Ok(Value::String(value.to_owned())),
+            _ => Err(format!("Unknown field '{}'. Expected one of: {}.", field, Self::FIELDS.join(", ")))
+        };
+
+        result.map_err(|e| Error::new("Invalid value.", Some(e)))
     }
 
     pub fn from_name(name: String) -> Self {
         Self {
             name,
+            namespace: None,
             description: String::new(),
             authors: Vec::<String>::new(),
             version: Version::new(0, 1, 0),
             license: String::from("MIT"),
+            license_file: None,
             homepage: None,
             repository: None,
-            dependencies: Some(Vec::new())
+            copyright: None,
+            dependencies: Some(Vec::new()),
+            sources: None
         }
     }
 
@@ -132,6 +237,17 @@ impl Project {
         }
     }
 
+    /// Returns the prefix for the project's script names, derived from its name if unset.
+    pub fn namespace_default(&self) -> String {
+        match &self.namespace {
+            Some(n) => n.clone(),
+            None => self.name.to_lowercase()
+                .chars()
+                .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                .collect()
+        }
+    }
+
     pub fn authors_default(&self) -> String {
         if self.authors.is_empty() {
             String::from("None")
@@ -146,27 +262,90 @@ impl Project {
 
     const README_ABOUT: &'static str = "## About\n\nProvide a brief explanation of your project and how it's used.";
     const README_EXAMPLE: &'static str = "## Example\n\nUse this space to demonstrate the usage of your project.\n> If the user only needs to worry about installation, consider omitting this section.";
+    const README_ORDER: [&'static str; 6] = ["header", "about", "setup", "example", "scripts", "license"];
     const README_DZP: &'static str = "Use [dzp](https://github.com/acikek/dzp-rs) for additional features.";
 
-    fn readme_setup(&self) -> String {
+    fn readme_setup(&self, config: &UserConfig) -> String {
         let repo = match &self.repository {
             Some(u) => u.to_string(),
-            None => "https://github.com/<user>/<name>".to_owned()
+            None => config.repository_pattern(&self.name)
+                .unwrap_or_else(|| "https://github.com/<user>/<name>".to_owned())
         };
 
         format!("## Setup\n\nClone using git:\n```sh\ngit clone {}\n```\n{}", repo, Self::README_DZP)
     }
 
-    fn readme_scripts(&self) -> String {
-        let scripts = get_script_types().iter()
-            .map(|(script_type, names)| {
+    fn readme_script(name: &str, script: &Script, scripts: &Scripts, detail: Detail) -> String {
+        let deprecated = if script.is_deprecated() { " *(deprecated)*" } else { "" };
+        let doc = script.doc.as_ref();
+
+        match detail {
+            Detail::Names => format!("- `{}`{}", name, deprecated),
+            Detail::Summary => {
+                match doc.and_then(|d| d.description.clone()) {
+                    Some(d) => format!("- `{}`{} — {}", name, deprecated, d),
+                    None => format!("- `{}`{}", name, deprecated)
+                }
+            }
+            Detail::Full => {
+                let mut sections = vec![format!("#### `{}`", name)];
+
+                if let Some(doc) = doc {
+                    if doc.is_deprecated() {
+                        sections.push(markdown::deprecation(doc, scripts, Links::Anchors));
+                    }
+
+                    if let Some(d) = &doc.description {
+                        sections.push(d.clone());
+                    }
+
+                    if let Some(d) = markdown::details(doc) {
+                        sections.push(d);
+                    }
+
+                    if let Some(u) = &doc.usage {
+                        sections.push(markdown::usage(u));
+                    }
+
+                    if let Some(e) = &doc.examples {
+                        sections.push(markdown::examples(e));
+                    }
+
+                    if let Some(d) = &doc.determine {
+                        sections.push(markdown::determine(d));
+                    }
+
+                    if let Some(defs) = &doc.defs {
+                        sections.push(markdown::arg_table("Definitions", defs));
+                    }
+
+                    if let Some(see) = &doc.see {
+                        sections.push(markdown::link_list("See also", see, scripts, Links::Anchors));
+                    }
+                }
+
+                sections.join("\n\n")
+            }
+        }
+    }
+
+    fn readme_scripts(&self, scripts: &Scripts, detail: Detail) -> String {
+        let sep = if detail == Detail::Full { "\n\n" } else { "\n" };
+
+        let scripts = script_types(scripts).iter()
+            .filter_map(|(script_type, names)| {
                 let t = titlecase(script_type);
                 let n = names.iter()
-                    .map(|s| format!("- `{}`", s))
-                    .collect::<Vec<String>>()
-                    .join("\n");
-
-                format!("### {}\n\n{}", t, n)
+                    .filter_map(|name| scripts.get(name).map(|s| (name, s)))
+                    .filter(|(_, s)| !s.is_private())
+                    .map(|(name, s)| Self::readme_script(name, s, scripts, detail))
+                    .collect::<Vec<String>>();
+
+                if n.is_empty() {
+                    None
+                } else {
+                    Some(format!("### {}\n\n{}", t, n.join(sep)))
+                }
             })
             .collect::<Vec<String>>()
             .join("\n\n");
@@ -175,20 +354,68 @@ impl Project {
     }
 
     pub fn readme_license(&self) -> String {
-        format!("## License\n\n{} © (year) (name)", self.license)
+        let mut files = self.license_files().0.into_iter()
+            .map(|(f, _)| f)
+            .collect::<Vec<String>>();
+
+        if let Some(f) = &self.license_file {
+            files.push(f.clone());
+        }
+
+        let notice = format!("## License\n\n{} © {} {}", self.license, year(), self.copyright_holder());
+
+        if files.is_empty() {
+            return notice;
+        }
+
+        let see = files.iter()
+            .map(|f| format!("[{}]({})", f, f))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!("{}\n\nSee {}.", notice, see)
+    }
+
+    /// The README sections dzp keeps up to date, keyed by region name.
+    ///
+    /// Without a repository, the setup section uses the user's repository pattern.
+    pub fn readme_regions(&self, scripts: &Scripts, detail: Detail, config: &UserConfig) -> Vec<(&'static str, String)> {
+        vec![
+            ("setup", self.readme_setup(config)),
+            ("scripts", self.readme_scripts(scripts, detail)),
+            ("license", self.readme_license())
+        ]
+    }
+
+    /// Every section of the default README, with dzp-managed sections wrapped in their markers.
+    ///
+    /// The scripts section's marker records the detail level unless it's the default, so it's kept when updating.
+    pub fn readme_sections(&self, scripts: &Scripts, detail: Detail, config: &UserConfig) -> BTreeMap<&'static str, String> {
+        let mut sections = BTreeMap::new();
+
+        sections.insert("header", self.readme_header());
+        sections.insert("about", Self::README_ABOUT.to_owned());
+        sections.insert("example", Self::README_EXAMPLE.to_owned());
+
+        for (name, content) in self.readme_regions(scripts, detail, config) {
+            let options = match name {
+                "scripts" if detail != Detail::Names => format!("detail={}", detail),
+                _ => String::new()
+            };
+
+            sections.insert(name, regions::wrap(name, &options, &content));
+        }
+
+        sections
     }
 
-    pub fn readme(&self) -> String {
-        let sections = vec![
-            self.readme_header(),
-            Self::README_ABOUT.to_owned(),
-            self.readme_setup(),
-            Self::README_EXAMPLE.to_owned(),
-            self.readme_scripts(),
-            self.readme_license()
-        ];
+    pub fn readme(&self, scripts: &Scripts, detail: Detail, config: &UserConfig) -> String {
+        let sections = self.readme_sections(scripts, detail, config);
 
-        sections.join("\n\n")
+        Self::README_ORDER.iter()
+            .filter_map(|name| sections.get(name).cloned())
+            .collect::<Vec<String>>()
+            .join("\n\n")
     }
 
     pub fn comment_key(key: &str, value: String) -> String {
@@ -204,8 +431,98 @@ impl Project {
         )
     }
 
-    pub fn license_text(&self) -> String {
-        from_id(&self.license).unwrap_or(&license::MIT).text().to_string()
+    pub fn copyright_holder(&self) -> String {
+        match &self.copyright {
+            Some(c) => c.clone(),
+            None if self.authors.is_empty() => format!("The {} Authors", self.name),
+            None => self.authors.join(", ")
+        }
+    }
+
+    const YEAR_PLACEHOLDERS: [&'static str; 7] = ["<year>", "<yyyy>", "<YEAR>", "<yyyy, yyyy>", "[year]", "[yyyy]", "[YEAR]"];
+    const HOLDER_PLACEHOLDERS: [&'static str; 10] = [
+        "<copyright holders>", "<copyright holder>", "<COPYRIGHT HOLDERS>", "<COPYRIGHT HOLDER>", "<owner>", "<name of author>",
+        "[copyright holders]", "[name of copyright owner]", "[name of author]", "[fullname]"
+    ];
+
+    /// Parses an SPDX license expression such as `MIT OR Apache-2.0`.
+    pub fn parse_license(expr: &str) -> Result<Expression, Error> {
+        Expression::parse_mode(expr, ParseMode::LAX).map_err(|e| Error::new("Invalid license.", Some(e.to_string())))
+    }
+
+    /// Returns the licenses in the project's license expression, with any exceptions.
+    pub fn license_components(&self) -> Vec<(String, Option<String>)> {
+        let expr = match Self::parse_license(&self.license) {
+            Ok(expr) => expr,
+            Err(_) => return vec![(self.license.clone(), None)]
+        };
+
+        let mut result = Vec::new();
+
+        for r in expr.requirements() {
+            let id = match &r.req.license {
+                LicenseItem::Spdx { id, .. } => id.name.to_owned(),
+                LicenseItem::Other { lic_ref, .. } => format!("LicenseRef-{}", lic_ref)
+            };
+
+            let component = (id, r.req.exception.map(|e| e.name.to_owned()));
+
+            if !result.contains(&component) {
+                result.push(component);
+            }
+        }
+
+        result
+    }
+
+    /// Returns the license files to generate as (file name, text) pairs.
+    ///
+    /// A single license is written to `LICENSE`, while each license in an expression
+    /// gets its own `LICENSE-<id>` file. Licenses without a known text, such as
+    /// `LicenseRef-` licenses, are returned separately.
+    pub fn license_files(&self) -> (Vec<(String, String)>, Vec<String>) {
+        let components = self.license_components();
+        let single = components.len() == 1;
+        let mut files = Vec::new();
+        let mut unknown = Vec::new();
+
+        for (id, exception) in components {
+            match self.license_text(&id, exception.as_deref()) {
+                Some(text) => {
+                    let file = if single { "LICENSE".to_owned() } else { format!("LICENSE-{}", id) };
+                    files.push((file, text));
+                }
+                None => unknown.push(id)
+            }
+        }
+
+        (files, unknown)
+    }
+
+    /// Returns whether a file name is one `license_files` could have generated.
+    pub fn is_license_file(name: &str) -> bool {
+        name == "LICENSE" || name.strip_prefix("LICENSE-").is_some_and(|id| from_id(id).is_some())
+    }
+
+    pub fn license_text(&self, id: &str, exception: Option<&str>) -> Option<String> {
+        let mut text = from_id(id)?.text().to_string();
+
+        if let Some(e) = exception.and_then(from_id_exception) {
+            text = format!("{}\n\n{}", text, e.text());
+        }
+
+        let year = year().to_string();
+        let holder = self.copyright_holder();
+
+        for p in Self::YEAR_PLACEHOLDERS.iter() {
+            text = text.replace(p, &year);
+        }
+
+        for p in Self::HOLDER_PLACEHOLDERS.iter() {
+            text = text.replace(p, &holder);
+        }
+
+        Some(text)
     }
 
     pub fn yaml(&self) -> String {
@@ -213,36 +530,78 @@ impl Project {
     }
 
     pub fn print_url(key: &str, url: Option<Url>) {
-        match url {
-            Some(u) => println!("{}: {}", key, u),
-            None => ()
+        if let Some(u) = url {
+            println!("{}: {}", key, u);
         }
     }
 
     pub fn print(&self) {
-        lazer()
-            .print(&self.name)
-            .print_ln(&format!(" v{}", self.version))
-            .iff(self.description.is_empty())
-                .print_ln("No description")
-            .el()
-                .print_ln(&self.description)
-            .end()
-            .print_ln("")
-            .iff(self.authors.len() > 1)
-                .print_ln(&format!("Authors: {}", self.authors.join(", ")))
-            .el()
-                .print_ln(&format!("Author: {}", self.authors.first().unwrap_or(&"None".to_owned())))
-            .end()
-            .print_ln(&format!("License: {}", self.license));
+        println!("{} v{}", self.name, self.version);
+        println!("{}\n", if self.description.is_empty() { "No description" } else { &self.description });
+
+        if self.authors.len() > 1 {
+            println!("Authors: {}", self.authors.join(", "));
+        } else {
+            println!("Author: {}", self.authors.first().map_or("None", String::as_str));
+        }
+
+        if let Some(namespace) = &self.namespace {
+            println!("Namespace: {}", namespace);
+        }
+
+        println!("License: {}", self.license);
+
+        if let Some(copyright) = &self.copyright {
+            println!("Copyright: {}", copyright);
+        }
 
         Self::print_url("Homepage", self.homepage.clone());
         Self::print_url("Repository", self.repository.clone());
 
-        lazer()
-            .iff(self.dependencies.is_some() && !self.dependencies.clone().unwrap().is_empty())
-                .print_ln("\nDependencies:")
-                .print_ln(&self.dependencies.clone().unwrap_or(Vec::new()).iter().map(|s| format!("- {}", s)).collect::<Vec<String>>().join("\n"))
-            .end();
+        if let Some(sources) = self.sources.as_ref().filter(|s| !s.is_empty()) {
+            println!("Sources: {}", sources.join(", "));
+        }
+
+        if let Some(dependencies) = self.dependencies.as_ref().filter(|d| !d.is_empty()) {
+            println!("\nDependencies:");
+
+            for dependency in dependencies {
+                println!("- {}", dependency);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+
+    use super::Bump;
+
+    fn bump(bump: Bump, version: &str) -> String {
+        bump.apply(&Version::parse(version).unwrap()).unwrap().to_string()
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn bumps_releases() {
+        assert_eq!(bump(Bump::Major, "1.2.3"), "2.0.0");
+        assert_eq!(bump(Bump::Minor, "1.2.3"), "1.3.0");
+        assert_eq!(bump(Bump::Patch, "1.2.3"), "1.2.4");
+    }
+
+    #[test]
+    fn releases_pre_releases() {
+        assert_eq!(bump(Bump::Patch, "1.0.1-beta.1"), "1.0.1");
+        assert_eq!(bump(Bump::Minor, "1.1.0-beta.2"), "1.1.0");
+        assert_eq!(bump(Bump::Minor, "1.1.1-beta.0"), "1.2.0");
+        assert_eq!(bump(Bump::Major, "2.0.0-rc.1"), "2.0.0");
+        assert_eq!(bump(Bump::Major, "2.1.0-rc.1"), "3.0.0");
+    }
+
+    #[test]
+    fn bumps_pre_releases() {
+        assert_eq!(bump(Bump::Prerelease("beta".to_owned()), "1.0.0"), "1.0.1-beta.0");
+        assert_eq!(bump(Bump::Prerelease("beta".to_owned()), "1.0.1-beta.1"), "1.0.1-beta.2");
+        assert_eq!(bump(Bump::Prerelease("rc".to_owned()), "1.0.1-beta.1"), "1.0.1-rc.0");
+    }
+}
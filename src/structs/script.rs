@@ -1,17 +1,21 @@
-use lazer::lazer;
 use serde::{Serialize, Deserialize};
-use serde_yaml::from_value;
+use serde_yaml::{from_value, Value};
 
+use super::command::Command;
 use super::doc::Doc;
 use crate::cache::scripts::ScriptContents;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Script {
     pub path: String,
     #[serde(rename = "type")]
     pub script_type: String,
     pub keys: Vec<String>,
-    pub doc: Option<Doc>
+    pub doc: Option<Doc>,
+    #[serde(default)]
+    pub events: Option<Vec<String>>,
+    #[serde(default)]
+    pub command: Option<Command>
 }
 
 impl Script {
@@ -20,28 +24,73 @@ impl Script {
             return Err("Script does not contain a 'type' key.".to_owned());
         }
 
-        match from_value(contents.get("type").unwrap().clone()) {
+        match from_value::<String>(contents.get("type").unwrap().clone()) {
             Ok(script_type) => {
-                let keys = contents.keys().map(|s| s.clone()).collect();
+                let keys = contents.keys().cloned().collect();
                 let doc = Doc::from(&contents);
+                let events = Self::events_from(&script_type, &contents);
+                let command = if script_type == "command" { Command::from(&contents) } else { None };
 
                 Ok(Self {
                     path,
                     script_type,
                     keys,
-                    doc
+                    doc,
+                    events,
+                    command
                 })
             },
             Err(_) => Err("Failed to parse 'type' key.".to_owned())
         }
     }
 
+    fn events_from(script_type: &str, contents: &ScriptContents) -> Option<Vec<String>> {
+        match contents.get("events") {
+            Some(Value::Mapping(map)) if script_type == "world" => Some(map.iter()
+                .filter_map(|(k, _)| k.as_str())
+                .map(String::from)
+                .collect()),
+            _ => None
+        }
+    }
+
+    /// Returns the name of the event an event line handles, without `on`/`after` and switches.
+    ///
+    /// For example, `after player joins flagged:vip` handles `player joins`.
+    pub fn event_name(line: &str) -> String {
+        line.split_whitespace()
+            .skip_while(|w| ["on", "after"].contains(w))
+            .filter(|w| !w.contains(':'))
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.doc.as_ref().is_some_and(Doc::is_deprecated)
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.doc.as_ref().and_then(|d| d.private).unwrap_or(false)
+    }
+
     pub fn print(&self, name: &str) {
-        lazer()
-            .print_ln(&format!("Name: {}", name))
-            .print_ln(&format!("Type: {}", self.script_type))
-            .print_ln(&format!("Path: {}", self.path))
-            .print_ln(&format!("Keys: {}", self.keys.join(", ")));
+        println!("Name: {}", name);
+        println!("Type: {}", self.script_type);
+        println!("Path: {}", self.path);
+        println!("Keys: {}", self.keys.join(", "));
+
+        if let Some(events) = &self.events {
+            println!("\nEvents:");
+
+            for event in events {
+                println!("- {}", event);
+            }
+        }
+
+        if let Some(command) = &self.command {
+            println!();
+            command.print();
+        }
 
         if let Some(doc) = &self.doc {
             println!();
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use super::project::Project;
+use crate::error::Error;
+
+/// The entry script created when a style doesn't provide one.
+const MAIN: &str = r#"{{name}}:
+  type: world
+  events:
+    after server start:
+    - debug log "Hello, world!"
+"#;
+
+/// A project style, describing the directories and files `dzp new` creates.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Style {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Directories to create, even if no files are placed in them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dirs: Vec<String>,
+    /// Paths of files to create mapped to their contents. Both may use variables.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+    /// The path of the project's main script.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>
+}
+
+impl Style {
+    /// Reads a style from a YAML manifest.
+    pub fn from_yaml(text: &str) -> Result<Self, Error> {
+        serde_yaml::from_str(text).map_err(|e| Error::new("Invalid style.", Some(e.to_string())))
+    }
+
+    /// Reads an older style, which lists one directory per line and places the entry script in the last.
+    pub fn from_dirs(text: &str) -> Self {
+        let dirs = text.split_terminator('\n')
+            .map(|d| d.trim().to_owned())
+            .filter(|d| !d.is_empty())
+            .collect::<Vec<String>>();
+
+        let entry = match dirs.last().map(String::as_str) {
+            Some(".") | None => "{{name}}.dsc".to_owned(),
+            Some(last) => format!("{}/{{{{name}}}}.dsc", last)
+        };
+
+        Self {
+            description: None,
+            dirs: dirs.into_iter().filter(|d| d != ".").collect(),
+            files: BTreeMap::new(),
+            entry: Some(entry)
+        }
+    }
+
+    /// Returns the values of the variables available to style files.
+    pub fn variables(project: &Project) -> BTreeMap<&'static str, String> {
+        let mut vars = BTreeMap::new();
+
+        vars.insert("name", project.name.clone());
+        vars.insert("namespace", project.namespace_default());
+        vars.insert("authors", project.authors_default());
+        vars.insert("description", project.description_default());
+        vars.insert("version", project.version.to_string());
+        vars.insert("license", project.license.clone());
+        vars
+    }
+
+    /// Replaces every `{{variable}}` in the text. Unknown variables are left as they are.
+    pub fn render(text: &str, vars: &BTreeMap<&'static str, String>) -> String {
+        let mut result = String::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find("{{") {
+            let value = rest[start + 2..].find("}}")
+                .and_then(|end| vars.get(rest[start + 2..start + 2 + end].trim()).map(|v| (end, v)));
+
+            match value {
+                Some((end, v)) => {
+                    result += &rest[..start];
+                    result += v;
+                    rest = &rest[start + 4 + end..];
+                }
+                None => {
+                    result += &rest[..start + 2];
+                    rest = &rest[start + 2..];
+                }
+            }
+        }
+
+        result + rest
+    }
+
+    /// Returns the directories to create for a project.
+    pub fn dirs(&self, project: &Project) -> Vec<String> {
+        let vars = Self::variables(project);
+
+        self.dirs.iter()
+            .map(|d| Self::render(d, &vars))
+            .collect()
+    }
+
+    /// Returns the path of the project's entry script, if the style has one.
+    pub fn entry(&self, project: &Project) -> Option<String> {
+        self.entry.as_ref().map(|e| Self::render(e, &Self::variables(project)))
+    }
+
+    /// Returns the paths and contents of the files to create for a project.
+    ///
+    /// If the entry script isn't one of the style's files, it's created with a world script.
+    pub fn files(&self, project: &Project) -> Vec<(String, String)> {
+        let vars = Self::variables(project);
+
+        let mut files = self.files.iter()
+            .map(|(path, contents)| (Self::render(path, &vars), Self::render(contents, &vars)))
+            .collect::<Vec<(String, String)>>();
+
+        if let Some(entry) = self.entry(project) {
+            if !files.iter().any(|(path, _)| *path == entry) {
+                files.push((entry, Self::render(MAIN, &vars)));
+            }
+        }
+
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{Project, Style};
+    use crate::structs::project::Preset;
+
+    fn project() -> Project {
+        Project::from_preset("My Pack".to_owned(), &Preset {
+            version: Some("1.2.0".parse().unwrap()),
+            license: Some("MIT".to_owned()),
+            ..Preset::default()
+        })
+    }
+
+    fn render(text: &str) -> String {
+        Style::render(text, &Style::variables(&project()))
+    }
+
+    #[test]
+    fn renders_known_variables() {
+        assert_eq!(render("{{name}} v{{ version }}"), "My Pack v1.2.0");
+        assert_eq!(render("{{namespace}}_command: {{license}}"), "my_pack_command: MIT");
+    }
+
+    #[test]
+    fn keeps_unknown_and_unclosed_variables() {
+        assert_eq!(render("{{unknown}} {{name}}"), "{{unknown}} My Pack");
+        assert_eq!(render("{{name}} {{name"), "My Pack {{name");
+        assert_eq!(render("{{{{name}}}}"), "{{My Pack}}");
+        assert_eq!(render("{{}} }}"), "{{}} }}");
+    }
+
+    #[test]
+    fn creates_missing_entry_script() {
+        let style = Style {
+            entry: Some("scripts/{{namespace}}.dsc".to_owned()),
+            files: BTreeMap::from([("scripts/util.dsc".to_owned(), "# {{name}}".to_owned())]),
+            ..Style::default()
+        };
+        let files = style.files(&project());
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0], ("scripts/util.dsc".to_owned(), "# My Pack".to_owned()));
+        assert_eq!(files[1].0, "scripts/my_pack.dsc");
+        assert!(files[1].1.starts_with("My Pack:\n  type: world"));
+    }
+
+    #[test]
+    fn keeps_entry_script_from_style() {
+        let style = Style {
+            entry: Some("{{namespace}}.dsc".to_owned()),
+            files: BTreeMap::from([("{{namespace}}.dsc".to_owned(), "{{namespace}}_main:".to_owned())]),
+            ..Style::default()
+        };
+
+        assert_eq!(style.files(&project()), [("my_pack.dsc".to_owned(), "my_pack_main:".to_owned())]);
+        assert!(Style::default().files(&project()).is_empty());
+    }
+}
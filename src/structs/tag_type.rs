@@ -0,0 +1,213 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Every known object type, with its object notation prefix and parent type.
+const TYPES: [(&str, Option<&str>, Option<&str>); 30] = [
+    ("ObjectTag", None, None),
+    ("ElementTag", Some("el"), None),
+    ("ListTag", Some("li"), None),
+    ("MapTag", Some("map"), None),
+    ("QueueTag", Some("q"), None),
+    ("ScriptTag", Some("s"), None),
+    ("DurationTag", Some("d"), None),
+    ("TimeTag", Some("time"), None),
+    ("BinaryTag", Some("binary"), None),
+    ("SecretTag", Some("secret"), None),
+    ("CustomObjectTag", Some("custom"), None),
+    ("JavaReflectedObjectTag", Some("reflected"), None),
+    ("EntityTag", Some("e"), None),
+    ("PlayerTag", Some("p"), Some("EntityTag")),
+    ("NPCTag", Some("n"), Some("EntityTag")),
+    ("LocationTag", Some("l"), None),
+    ("ItemTag", Some("i"), None),
+    ("MaterialTag", Some("m"), None),
+    ("WorldTag", Some("w"), None),
+    ("ChunkTag", Some("ch"), None),
+    ("AreaObject", None, None),
+    ("CuboidTag", Some("cu"), Some("AreaObject")),
+    ("EllipsoidTag", Some("ellipsoid"), Some("AreaObject")),
+    ("PolygonTag", Some("polygon"), Some("AreaObject")),
+    ("ColorTag", Some("co"), None),
+    ("InventoryTag", Some("in"), None),
+    ("PluginTag", Some("pl"), None),
+    ("TradeTag", Some("trade"), None),
+    ("BiomeTag", Some("b"), None),
+    ("EnchantmentTag", Some("enchantment"), None)
+];
+
+/// Object types whose plain values may be numbers, e.g. `5` for five seconds.
+const NUMERIC: [&str; 2] = ["DurationTag", "TimeTag"];
+
+/// The subtypes of `ElementTag`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind {
+    Boolean,
+    Number,
+    Decimal
+}
+
+/// A parsed kDoc argument type, such as `ElementTag(Boolean)` or `ListTag(PlayerTag)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagType {
+    Element(Option<ElementKind>),
+    List(Option<Box<TagType>>),
+    Object(&'static str)
+}
+
+fn known(name: &str) -> Option<&'static str> {
+    TYPES.iter()
+        .map(|(n, _, _)| *n)
+        .find(|n| n.eq_ignore_ascii_case(name))
+}
+
+/// Returns the type a value written in object notation (e.g. `p@...`) belongs to.
+fn notation(literal: &str) -> Option<&'static str> {
+    let (prefix, _) = literal.split_once('@')?;
+
+    TYPES.iter()
+        .find(|(_, p, _)| p.is_some_and(|p| p.eq_ignore_ascii_case(prefix)))
+        .map(|(n, _, _)| *n)
+}
+
+/// Returns whether `name` is `parent` or one of its subtypes.
+fn is_subtype(name: &str, parent: &str) -> bool {
+    if name == parent || parent == "ObjectTag" {
+        return true;
+    }
+
+    TYPES.iter()
+        .find(|(n, _, _)| *n == name)
+        .and_then(|(_, _, p)| *p)
+        .is_some_and(|p| is_subtype(p, parent))
+}
+
+impl TagType {
+    /// Returns the name of the object type, without any subtype.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Element(_) => "ElementTag",
+            Self::List(_) => "ListTag",
+            Self::Object(name) => name
+        }
+    }
+
+    /// Returns whether a literal value could be of this type.
+    ///
+    /// Only obvious mismatches are rejected, such as text passed as a boolean.
+    /// Values containing tags are always accepted, since they aren't known until runtime.
+    pub fn accepts(&self, literal: &str) -> bool {
+        let literal = literal.trim();
+
+        if literal.is_empty() || literal.contains('<') {
+            return true;
+        }
+
+        let boolean = literal.eq_ignore_ascii_case("true") || literal.eq_ignore_ascii_case("false");
+        let number = literal.parse::<f64>().is_ok();
+        let object = notation(literal);
+
+        match self {
+            Self::Element(Some(ElementKind::Boolean)) => boolean,
+            Self::Element(Some(_)) => number,
+            Self::Element(None) | Self::List(_) => true,
+            Self::Object(name) => match object {
+                Some(o) => is_subtype(o, name),
+                None => *name == "ObjectTag" || !(boolean || (number && !NUMERIC.contains(name)))
+            }
+        }
+    }
+}
+
+impl FromStr for TagType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (name, sub) = match s.split_once('(') {
+            Some((name, rest)) => match rest.strip_suffix(')') {
+                Some(sub) => (name.trim(), Some(sub.trim())),
+                None => return Err(format!("'{}' is missing a closing parenthesis", s))
+            },
+            None => (s, None)
+        };
+
+        let name = known(name).ok_or_else(|| format!("'{}' is not a known type", name))?;
+
+        match (name, sub) {
+            (_, Some("")) => Err(format!("'{}' has an empty subtype", s)),
+            ("ElementTag", Some(sub)) => match sub.to_lowercase().as_str() {
+                "boolean" => Ok(Self::Element(Some(ElementKind::Boolean))),
+                "number" | "integer" => Ok(Self::Element(Some(ElementKind::Number))),
+                "decimal" => Ok(Self::Element(Some(ElementKind::Decimal))),
+                _ => Err(format!("'{}' is not an ElementTag subtype; use Boolean, Number or Decimal", sub))
+            },
+            ("ListTag", Some(sub)) => Ok(Self::List(Some(Box::new(sub.parse()?)))),
+            (_, Some(_)) => Err(format!("{} does not take a subtype", name)),
+            ("ElementTag", None) => Ok(Self::Element(None)),
+            ("ListTag", None) => Ok(Self::List(None)),
+            (_, None) => Ok(Self::Object(name))
+        }
+    }
+}
+
+impl fmt::Display for TagType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Element(Some(kind)) => write!(f, "ElementTag({:?})", kind),
+            Self::List(Some(sub)) => write!(f, "ListTag({})", sub),
+            _ => write!(f, "{}", self.name())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ElementKind, TagType};
+
+    fn parse(s: &str) -> TagType {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_nested_types() {
+        assert_eq!(parse("MapTag"), TagType::Object("MapTag"));
+        assert_eq!(parse(" listtag ( playertag ) "), TagType::List(Some(Box::new(TagType::Object("PlayerTag")))));
+        assert_eq!(
+            parse("ListTag(ListTag(ElementTag(Integer)))"),
+            TagType::List(Some(Box::new(TagType::List(Some(Box::new(TagType::Element(Some(ElementKind::Number))))))))
+        );
+        assert_eq!(parse("ListTag(ListTag(MapTag))").to_string(), "ListTag(ListTag(MapTag))");
+    }
+
+    #[test]
+    fn rejects_invalid_types() {
+        for s in ["", "Player", "PlayerTag|NPCTag", "ListTag(PlayerTag|NPCTag)", "ListTag(", "ListTag()",
+                  "MapTag(ElementTag)", "ElementTag(Text)", "ListTag(ListTag(Foo))"] {
+            assert!(s.parse::<TagType>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn accepts_numbers_and_booleans() {
+        let boolean = parse("ElementTag(Boolean)");
+        let number = parse("ElementTag(Decimal)");
+
+        assert!(boolean.accepts("TRUE") && !boolean.accepts("1") && !boolean.accepts("yes"));
+        assert!(number.accepts("-1.5") && !number.accepts("true") && !number.accepts("five"));
+        assert!(parse("DurationTag").accepts("5") && !parse("LocationTag").accepts("5"));
+        assert!(!parse("PlayerTag").accepts("false") && parse("ObjectTag").accepts("false"));
+        assert!(boolean.accepts("<[flag]>") && number.accepts(""));
+    }
+
+    #[test]
+    fn accepts_lists_and_maps() {
+        let map = parse("MapTag");
+        let players = parse("ListTag(PlayerTag)");
+
+        assert!(map.accepts("map@[a=1]") && map.accepts("[a=1;b=2]"));
+        assert!(!map.accepts("li@a|b") && !map.accepts("3") && !map.accepts("true"));
+        assert!(players.accepts("p@abc|p@def") && players.accepts("5") && parse("ListTag").accepts("a|b"));
+        assert!(parse("EntityTag").accepts("p@abc") && !parse("PlayerTag").accepts("e@zombie"));
+    }
+}
@@ -1,5 +1,30 @@
+use dzp::cache::scripts::{get_scripts, Scripts};
+use dzp::io::log::err;
+
+pub mod bump;
 pub mod compile;
+pub mod doc;
+pub mod init;
+pub mod input;
+pub mod license;
 pub mod new;
 pub mod project;
 pub mod readme;
-pub mod script;
\ No newline at end of file
+pub mod script;
+pub mod search;
+pub mod style;
+
+/// Announces a file or directory that's about to be created.
+pub fn creating(path: &str) {
+    println!("Creating {}...", path);
+}
+
+pub fn scripts(force: bool) -> Scripts {
+    let (scripts, errors) = get_scripts(".", force);
+
+    for e in errors {
+        err(&e, None);
+    }
+
+    scripts
+}
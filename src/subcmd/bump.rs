@@ -0,0 +1,116 @@
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+use clap::ArgMatches;
+use git2::Repository;
+use serde_yaml::Value;
+
+use dzp::Error;
+use dzp::cache::scripts::{is_ignored, script_files};
+use dzp::io::{changelog::add_release, date::date, fs::{get_project, project_path}, git::{commit_and_tag, tag_exists}, header::set_title};
+use dzp::structs::project::{Bump, Project};
+
+use super::project::update;
+
+/// Opens the project's repository and checks that it can be released to.
+fn open_repo(tag: &str) -> Result<Repository, Error> {
+    let repo = Repository::discover(".")
+        .map_err(|_| Error::new("Not in a git repository; use --no-commit to skip the commit and tag.", None))?;
+
+    if tag_exists(&repo, tag) {
+        return Err(Error::new("Tag already exists.", Some(tag.to_owned())));
+    }
+
+    if let Err(e) = repo.signature() {
+        return Err(Error::new("No git identity to commit with.", Some(e.message().to_owned())));
+    }
+
+    Ok(repo)
+}
+
+/// Updates the title line in the header of every script file, keeping the previous contents of the files changed.
+fn update_headers(name: &str, title: &str, backups: &mut Vec<(PathBuf, String)>) {
+    for path in script_files(".") {
+        let contents = match read_to_string(&path) {
+            Ok(contents) if !is_ignored(&contents) => contents,
+            _ => continue
+        };
+
+        if let Some(updated) = set_title(&contents, name, title) {
+            println!("Updating {}...", path.display());
+
+            match write(&path, updated) {
+                Ok(_) => backups.push((path, contents)),
+                Err(e) => Error::new("Failed to update file.", Some(e.to_string())).log()
+            }
+        }
+    }
+}
+
+/// Writes the new version to the project's files and commits them, keeping the previous contents of every file changed.
+fn release(project: &Project, tag: &str, repo: Option<Repository>, backups: &mut Vec<(PathBuf, String)>) -> Result<(), Error> {
+    let file = project_path(None);
+    let text = read_to_string(&file).map_err(|e| Error::new("Failed to read project file.", Some(e.to_string())))?;
+
+    update(None, &[("version", Value::String(project.version.to_string()))])?;
+    backups.push((file, text));
+
+    update_headers(&project.name, &project.title(), backups);
+
+    // The changelog is only updated if the project has one
+    let changelog = Path::new("CHANGELOG.md");
+
+    if let Ok(text) = read_to_string(changelog) {
+        println!("Updating {}...", changelog.display());
+
+        write(changelog, add_release(&text, &project.version.to_string(), &date()))
+            .map_err(|e| Error::new("Failed to update changelog.", Some(e.to_string())))?;
+        backups.push((changelog.to_path_buf(), text));
+    }
+
+    if let Some(repo) = repo {
+        let files = backups.iter().map(|(path, _)| path.clone()).collect::<Vec<PathBuf>>();
+
+        commit_and_tag(&repo, &files, &format!("Release {}", tag), tag, &project.title())?;
+        println!("Committed and tagged {}.", tag);
+    }
+
+    Ok(())
+}
+
+fn bump_version(matches: &ArgMatches) -> Result<(), Error> {
+    let mut project = get_project(None)?;
+
+    let bump = Bump::from_part(matches.value_of("PART").unwrap(), matches.value_of("ID"))?;
+    let version = bump.apply(&project.version)?;
+    let tag = format!("v{}", version);
+
+    // Nothing is changed if the release can't be committed
+    let repo = if matches.is_present("no-commit") { None } else { Some(open_repo(&tag)?) };
+
+    let previous = project.version.clone();
+    project.version = version;
+
+    // Every file is restored if the release fails partway
+    let mut backups = Vec::new();
+
+    if let Err(e) = release(&project, &tag, repo, &mut backups) {
+        for (path, contents) in &backups {
+            let _ = write(path, contents);
+        }
+
+        println!("Restored {} file(s).", backups.len());
+        return Err(e);
+    }
+
+    println!("Bumped version from {} to {}.", previous, project.version);
+    Ok(())
+}
+
+pub fn bump(matches: &ArgMatches) {
+    if let Err(e) = bump_version(matches) {
+        e.log();
+        exit(1);
+    }
+}
@@ -1,24 +1,27 @@
 use clap::ArgMatches;
 
-use crate::cache::scripts::script_contents;
-use crate::io::fs::{create, get_project};
+use dzp::cache::scripts::compile as compile_scripts;
+use dzp::io::fs::{create, get_project};
 
-pub fn compile(matches: &ArgMatches) {
-    let scripts = script_contents().join("\n");
-    let mut header = String::new();
+use super::creating;
 
-    let file = match matches.value_of("FILE") {
-        Some(file) => file.to_owned(),
+pub fn compile(matches: &ArgMatches) {
+    let (file, project) = match matches.value_of("FILE") {
+        Some(file) => (file.to_owned(), None),
         None => {
-            if let Ok(project) = get_project(None) {
-                header = project.comment_header() + "\n\n";
-                project.name + ".dsc"
-            } else {
-                "compiled.dsc".to_owned()
+            match get_project(None) {
+                Ok(project) => (project.name.clone() + ".dsc", Some(project)),
+                Err(e) => {
+                    e.log();
+                    ("compiled.dsc".to_owned(), None)
+                }
             }
         }
     };
 
-    let content = format!("#: ignore\n\n{}{}", header, scripts);
-    create(&file, content, true)
-}
\ No newline at end of file
+    creating(&file);
+
+    if let Err(e) = create(&file, compile_scripts(".", project.as_ref())) {
+        e.log();
+    }
+}
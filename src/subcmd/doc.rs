@@ -0,0 +1,145 @@
+use std::path::Path;
+use std::process::exit;
+
+use clap::{ArgMatches, Error as ClapError, ErrorKind};
+
+use dzp::analysis::{check::check as check_docs, coverage::{coverage as doc_coverage, Tally}};
+use dzp::cache::scripts::find_scripts_raw;
+use dzp::docgen::{html, json, markdown};
+use dzp::io::{fs::{create_all, get_project}, log::{err, warn}};
+
+use super::{creating, scripts};
+
+/// Reports invalid kDoc, returning whether there were no problems.
+fn check() -> bool {
+    let issues = check_docs(&find_scripts_raw("."));
+
+    if issues.is_empty() {
+        println!("All kDoc is valid.");
+        return true;
+    }
+
+    for issue in &issues {
+        let log = if issue.warning { warn } else { err };
+        log(&format!("{} ({}):", issue.script, issue.path), Some(issue.message.clone()));
+    }
+
+    let warnings = issues.iter().filter(|i| i.warning).count();
+    let problems = issues.len() - warnings;
+
+    println!("\n{} problem(s) and {} warning(s) found.", problems, warnings);
+    problems == 0
+}
+
+fn print_tally(label: &str, width: usize, tally: &Tally) {
+    println!(
+        "  {:width$}  {:>6.1}%  {}/{} scripts documented",
+        label, tally.percent(), tally.documented, tally.scripts, width = width
+    );
+}
+
+/// Reports documentation coverage, returning whether it meets the minimum.
+fn coverage(min: Option<&str>) -> bool {
+    let min = match min.map(|m| m.trim_end_matches('%').parse::<f64>()) {
+        Some(Ok(m)) => Some(m),
+        Some(Err(e)) => {
+            err("Invalid minimum coverage.", Some(e.to_string()));
+            exit(1);
+        }
+        None => None
+    };
+
+    let result = doc_coverage(&find_scripts_raw("."));
+
+    let width = result.by_file.keys()
+        .chain(result.by_type.keys())
+        .map(|k| k.len())
+        .max()
+        .unwrap_or_default();
+
+    println!("By file:");
+    for (path, tally) in &result.by_file {
+        print_tally(path, width, tally);
+    }
+
+    println!("\nBy type:");
+    for (script_type, tally) in &result.by_type {
+        print_tally(script_type, width, tally);
+    }
+
+    let missing = result.scripts.iter()
+        .filter(|s| !s.missing.is_empty())
+        .collect::<Vec<_>>();
+
+    if !missing.is_empty() {
+        println!("\nMissing:");
+        for s in missing {
+            let undocumented = if s.documented { "" } else { " (no kDoc)" };
+            println!("  {}{}: {}", s.name, undocumented, s.missing.join(", "));
+        }
+    }
+
+    let total = result.total;
+    println!(
+        "\nTotal: {:.1}% ({}/{} fields, {}/{} scripts documented)",
+        total.percent(), total.present, total.fields, total.documented, total.scripts
+    );
+
+    match min {
+        Some(min) if total.percent() < min => {
+            err("Documentation coverage is below the minimum.", Some(format!("{:.1}% < {}%", total.percent(), min)));
+            false
+        }
+        _ => true
+    }
+}
+
+pub fn doc(matches: &ArgMatches) {
+    let (checked, covered) = (matches.is_present("check"), matches.is_present("coverage"));
+
+    // Both reports run when both are asked for, and either failing fails the command
+    if checked || covered {
+        let mut passed = true;
+
+        if checked {
+            passed &= check();
+        }
+
+        if covered {
+            if checked {
+                println!();
+            }
+
+            passed &= coverage(matches.value_of("min"));
+        }
+
+        if !passed {
+            exit(1);
+        }
+
+        return;
+    }
+
+    if matches.is_present("split") && matches.value_of("format") != Some("markdown") {
+        ClapError::with_description("--split can only be used with --format markdown", ErrorKind::ArgumentConflict).exit();
+    }
+
+    let output = matches.value_of("output").unwrap_or("docs");
+    // Documentation is always generated from the scripts as they are now, the same as --check
+    let scripts = scripts(true);
+    let project = get_project(None).ok();
+
+    let files = match matches.value_of("format") {
+        Some("markdown") => markdown::generate(project.as_ref(), &scripts, matches.is_present("split")),
+        Some("json") => json::generate(project.as_ref(), &scripts),
+        _ => html::generate(project.as_ref(), &scripts)
+    };
+
+    for (file, _) in &files {
+        creating(&Path::new(output).join(file).to_string_lossy());
+    }
+
+    if let Err(e) = create_all(output, files) {
+        e.log();
+    }
+}
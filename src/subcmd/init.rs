@@ -0,0 +1,79 @@
+use std::env::current_dir;
+use std::path::Path;
+
+use clap::ArgMatches;
+use git2::Repository;
+
+use dzp::cache::scripts::script_roots;
+use dzp::config::user::read_config;
+use dzp::io::{log::err, fs::{create_dir, create, project_path}};
+use dzp::structs::project::{Preset, Project};
+
+use super::creating;
+use super::input::{cancelled, input_project, input_sources};
+
+/// Prints whether a file the project would normally have already exists.
+fn detect(name: &str, found: bool) {
+    if found {
+        println!("Found {}, leaving it as is.", name);
+    } else {
+        println!("No {} found.", name);
+    }
+}
+
+pub fn init(matches: &ArgMatches) {
+    if project_path(None).exists() {
+        return err("Project already initialized.", Some(project_path(None).to_string_lossy().into_owned()));
+    }
+
+    // Name of the project, from the directory
+    let dir = current_dir().unwrap();
+    let name = match matches.value_of("name") {
+        Some(name) => name.to_owned(),
+        None => dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| "project".to_owned())
+    };
+
+    detect("README.md", Path::new("README.md").exists());
+    detect("LICENSE", Path::new("LICENSE").exists() || Path::new("LICENSE.md").exists());
+    detect("git repository", Repository::discover(&dir).is_ok());
+
+    // Script directories, suggested from where .dsc files live
+    let proposed = script_roots(".");
+
+    if proposed.is_empty() {
+        println!("No scripts found.");
+    } else {
+        println!("Scripts found in: {}", proposed.join(", "));
+    }
+
+    println!();
+
+    // The project data object
+    // Take input if default argument not supplied, otherwise use the user's defaults
+    let defaults = read_config().unwrap_or_default().defaults(&name);
+
+    let project = if matches.is_present("default") {
+        Ok((Project::from_preset(name, &defaults), proposed))
+    } else {
+        input_sources(&proposed)
+            .and_then(|sources| input_project(name, &Preset::default(), &defaults).map(|p| (p, sources)))
+    };
+
+    let (mut project, sources) = match project {
+        Ok(project) => project,
+        Err(e) if cancelled(&e) => return,
+        Err(e) => return err("Failed to read input.", Some(e.to_string()))
+    };
+
+    if !sources.is_empty() && sources != ["."] {
+        project.sources = Some(sources);
+    }
+
+    // Create dzp directory
+    creating(".dzp");
+    creating(".dzp/project");
+
+    if let Err(e) = create_dir(".dzp").and_then(|_| create(".dzp/project", project.yaml())) {
+        e.log();
+    }
+}
@@ -0,0 +1,135 @@
+//! Prompts for project details.
+
+use rustyline::Editor;
+use rustyline::error::ReadlineError;
+use semver::Version;
+use url::Url;
+
+use dzp::io::log::err;
+use dzp::structs::project::{Preset, Project};
+
+/// Returns whether a prompt was cancelled with Ctrl-C or Ctrl-D, rather than failing.
+pub fn cancelled(e: &ReadlineError) -> bool {
+    matches!(e, ReadlineError::Interrupted | ReadlineError::Eof)
+}
+
+fn input(text: &str, rl: &mut Editor<()>) -> Result<String, ReadlineError> {
+    let line = rl.readline(&format!("{}: ", text))?;
+    rl.add_history_entry(&line);
+    Ok(line.trim().to_string())
+}
+
+/// Asks for a value, showing the default that's used if nothing is entered.
+fn input_default(text: &str, default: Option<&str>, rl: &mut Editor<()>) -> Result<String, ReadlineError> {
+    match default {
+        Some(d) if !d.is_empty() => Ok(Some(input(&format!("{} ({})", text, d), rl)?)
+            .filter(|i| !i.is_empty())
+            .unwrap_or_else(|| d.to_owned())),
+        _ => input(text, rl)
+    }
+}
+
+fn input_authors(default: Option<&Vec<String>>, rl: &mut Editor<()>) -> Result<Vec<String>, ReadlineError> {
+    let input = input_default("Authors", default.map(|a| a.join(", ")).as_deref(), rl)?;
+
+    Ok(input
+        .split_terminator(',')
+        .map(|s| String::from(s.trim()))
+        .filter(|s| !s.is_empty())
+        .collect())
+}
+
+fn input_version(default: Option<&Version>, rl: &mut Editor<()>) -> Result<Version, ReadlineError> {
+    let default = default.cloned().unwrap_or_else(|| Version::new(0, 1, 0));
+
+    loop {
+        let input = input_default("Version", Some(&default.to_string()), rl)?;
+
+        match Version::parse(&input) {
+            Ok(version) => return Ok(version),
+            Err(_) => err("Invalid version.", None)
+        }
+    }
+}
+
+/// Asks for a URL, where `-` clears the default.
+fn input_url(text: &str, default: Option<&Url>, rl: &mut Editor<()>) -> Result<Option<Url>, ReadlineError> {
+    loop {
+        let input = input_default(text, default.map(Url::as_str), rl)?;
+        if input.is_empty() || input == "-" { return Ok(None) }
+
+        match Url::parse(&input) {
+            Ok(url) => return Ok(Some(url)),
+            Err(_) => err("Invalid URL.", None)
+        }
+    }
+}
+
+fn input_license(default: Option<&str>, rl: &mut Editor<()>) -> Result<String, ReadlineError> {
+    loop {
+        let input = input_default("License", Some(default.unwrap_or("MIT")), rl)?;
+
+        match Project::parse_license(&input) {
+            Ok(_) => return Ok(input),
+            Err(e) => e.log()
+        }
+    }
+}
+
+/// Asks which directories hold the project's scripts, suggesting `proposed`.
+pub fn input_sources(proposed: &[String]) -> Result<Vec<String>, ReadlineError> {
+    let mut rl = Editor::<()>::new();
+    let input = input(&format!("Script directories ({})", proposed.join(", ")), &mut rl)?;
+
+    if input.is_empty() {
+        return Ok(proposed.to_vec());
+    }
+
+    Ok(input
+        .split_terminator(',')
+        .map(|s| String::from(s.trim().trim_end_matches('/')))
+        .filter(|s| !s.is_empty())
+        .collect())
+}
+
+/// Asks for every detail the preset doesn't give, suggesting the defaults.
+pub fn input_project(name: String, preset: &Preset, defaults: &Preset) -> Result<Project, ReadlineError> {
+    let mut rl = Editor::<()>::new();
+
+    let description = match &preset.description {
+        Some(d) => d.clone(),
+        None => input_default("Description", defaults.description.as_deref(), &mut rl)?
+    };
+    let authors = match &preset.authors {
+        Some(a) => a.clone(),
+        None => input_authors(defaults.authors.as_ref(), &mut rl)?
+    };
+    let version = match &preset.version {
+        Some(v) => v.clone(),
+        None => input_version(defaults.version.as_ref(), &mut rl)?
+    };
+    let license = match &preset.license {
+        Some(l) => l.clone(),
+        None => input_license(defaults.license.as_deref(), &mut rl)?
+    };
+    let homepage = match &preset.homepage {
+        Some(u) => Some(u.clone()),
+        None => input_url("Homepage", defaults.homepage.as_ref(), &mut rl)?
+    };
+    let repository = match &preset.repository {
+        Some(u) => Some(u.clone()),
+        None => input_url("Repository", defaults.repository.as_ref(), &mut rl)?
+    };
+
+    println!();
+
+    Ok(Project::from_preset(name, &Preset {
+        namespace: preset.namespace.clone(),
+        description: Some(description),
+        authors: Some(authors),
+        version: Some(version),
+        license: Some(license),
+        homepage,
+        repository
+    }))
+}
@@ -0,0 +1,97 @@
+use std::fs::{read_dir, read_to_string, remove_file, write};
+use std::path::Path;
+
+use clap::ArgMatches;
+
+use dzp::cache::scripts::{is_ignored, script_files};
+use dzp::io::{fs::{create, get_project}, header::set_key, log::err};
+use dzp::structs::project::Project;
+
+use super::creating;
+
+pub fn write_licenses(project: &Project) {
+    let (files, unknown) = project.license_files();
+
+    for (file, text) in &files {
+        creating(file);
+
+        if let Err(e) = create(file, text.clone()) {
+            e.log();
+        }
+    }
+
+    // Without a license file, a leftover LICENSE may hold the text of a custom license
+    if unknown.is_empty() || project.license_file.is_some() {
+        remove_stale(project, &files);
+    }
+
+    check_licenses(project, &unknown);
+}
+
+/// Removes license files written for a previous license that the project no longer uses.
+fn remove_stale(project: &Project, files: &[(String, String)]) {
+    let entries = match read_dir(".") {
+        Ok(entries) => entries,
+        Err(_) => return
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        let stale = Project::is_license_file(&name)
+            && !files.iter().any(|(file, _)| *file == name)
+            && project.license_file.as_deref().is_none_or(|f| Path::new(f) != Path::new(&name));
+
+        if !stale || !entry.path().is_file() {
+            continue;
+        }
+
+        println!("Removing {}...", name);
+
+        if let Err(e) = remove_file(entry.path()) {
+            err("Failed to remove file.", Some(e.to_string()));
+        }
+    }
+}
+
+/// Reports licenses the project's license files don't cover.
+pub fn check_licenses(project: &Project, unknown: &[String]) {
+    match &project.license_file {
+        Some(f) if !Path::new(f).exists() => err("License file does not exist.", Some(f.clone())),
+        Some(_) => (),
+        None if !unknown.is_empty() => {
+            err("No license text for some licenses; set 'license_file' to your own.", Some(unknown.join(", ")))
+        }
+        None => ()
+    }
+}
+
+pub fn license(matches: &ArgMatches) {
+    let project = match get_project(None) {
+        Ok(project) => project,
+        Err(e) => return e.log()
+    };
+
+    write_licenses(&project);
+
+    if !matches.is_present("headers") {
+        return;
+    }
+
+    for path in script_files(".") {
+        let contents = match read_to_string(&path) {
+            Ok(contents) if !is_ignored(&contents) => contents,
+            _ => continue
+        };
+
+        let updated = set_key(&contents, "license", &project.license);
+
+        if updated != contents {
+            println!("Updating {}...", path.display());
+
+            if let Err(e) = write(&path, updated) {
+                err("Failed to update file.", Some(e.to_string()));
+            }
+        }
+    }
+}
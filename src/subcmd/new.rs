@@ -1,57 +1,274 @@
 use std::env::current_dir;
+use std::io::{stdin, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use std::str::FromStr;
 
+use ansi_term::Color::Yellow;
 use clap::ArgMatches;
 use git2::Repository;
 
-use crate::config::styles::read_style;
-use crate::io::{log::err, fs::{create_dir, create}};
-use crate::structs::project::Project;
+use dzp::Error;
+use dzp::cache::scripts::Scripts;
+use dzp::config::{styles::read_style, user::{read_config, UserConfig}};
+use dzp::io::{log::{err, paint, warn}, fs::Changes, template::{clean, fetch, files}};
+use dzp::structs::project::{Detail, Preset, Project};
+use dzp::structs::style::Style;
 
-const MAIN: &str = r#"
-  type: world
-  events:
-    after server start:
-    - debug log "Hello, world!""#;
+use super::creating;
+use super::input::{cancelled, input_project};
+use super::license::check_licenses;
+
+/// Where a new project's files come from.
+enum Source {
+    Style(Style),
+    Template(PathBuf)
+}
+
+/// The directories and files a new project is made of.
+struct Plan {
+    dirs: Vec<String>,
+    files: Vec<(String, Vec<u8>)>,
+    /// Licenses the generated license files have no text for.
+    unknown: Vec<String>
+}
+
+impl Plan {
+    fn new(source: &Source, project: &Project, config: &UserConfig) -> Result<Self, Error> {
+        let (dirs, mut files) = match source {
+            Source::Style(style) => {
+                let files = style.files(project).into_iter()
+                    .map(|(path, contents)| (path, contents.into_bytes()))
+                    .collect();
+
+                (style.dirs(project), files)
+            }
+            Source::Template(dir) => (Vec::new(), files(dir, project)?)
+        };
+
+        let mut unknown = Vec::new();
+
+        // README & LICENSE, unless the template has its own
+        // The README has the dzp-managed sections, so `dzp readme` can fill in the scripts later
+        if !files.iter().any(|(f, _)| f == "README.md") {
+            files.push(("README.md".to_owned(), project.readme(&Scripts::new(), Detail::Names, config).into_bytes()));
+        }
+
+        if !files.iter().any(|(f, _)| f.starts_with("LICENSE")) {
+            let (licenses, missing) = project.license_files();
+            files.extend(licenses.into_iter().map(|(f, text)| (f, text.into_bytes())));
+            unknown = missing;
+        }
+
+        files.push((".dzp/project".to_owned(), project.yaml().into_bytes()));
+
+        Ok(Self { dirs, files, unknown })
+    }
+
+    fn names(&self) -> Vec<&str> {
+        self.files.iter().map(|(f, _)| f.as_str()).collect()
+    }
+
+    /// Returns the files that already exist.
+    fn conflicts(&self) -> Vec<&str> {
+        self.names().into_iter()
+            .filter(|f| Path::new(f).exists())
+            .collect()
+    }
+
+    /// Prints the files to create, failing if any of them exist.
+    ///
+    /// Existing files are only overwritten with `force`.
+    fn review(&self, force: bool) -> Result<(), Error> {
+        let conflicts = self.conflicts();
+        self.print(&conflicts);
+
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+
+        if !force {
+            return Err(Error::new("Some files already exist; use --force to overwrite them.", Some(conflicts.join(", "))));
+        }
+
+        warn("Overwriting existing files.", Some(conflicts.join(", ")));
+        Ok(())
+    }
+
+    fn print(&self, conflicts: &[&str]) {
+        println!("\nFiles to create:");
+
+        for (file, _) in &self.files {
+            if conflicts.contains(&file.as_str()) {
+                println!("  {} {}", file, paint(Yellow, "(exists)"));
+            } else {
+                println!("  {}", file);
+            }
+        }
+
+        println!();
+    }
+
+    fn write(&self, changes: &mut Changes, git: bool) -> Result<(), Error> {
+        for dir in &self.dirs {
+            if !Path::new(dir).exists() {
+                creating(dir);
+            }
+
+            changes.dir(Path::new(dir))?;
+        }
+
+        for (file, contents) in &self.files {
+            creating(file);
+            changes.file(Path::new(file), contents)?;
+        }
+
+        if !git {
+            return Ok(());
+        }
+
+        // Initialize git repository unless the project is already in one
+        let dir = current_dir().map_err(|e| Error::new("Failed to read the current directory.", Some(e.to_string())))?;
+
+        if Repository::discover(&dir).is_err() {
+            let git = dir.join(".git");
+            let existed = git.exists();
+            let result = Repository::init(&dir);
+
+            if !existed && git.exists() {
+                changes.track(&git);
+            }
+
+            result.map_err(|e| Error::new("Failed to initialize git repository.", Some(e.message().to_owned())))?;
+        } else {
+            println!("Using the existing git repository.");
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a flag's value, if it was given.
+fn parse<T: FromStr>(matches: &ArgMatches, name: &str) -> Result<Option<T>, String>
+where T::Err: ToString {
+    matches.value_of(name)
+        .map(|v| v.parse::<T>().map_err(|e| format!("--{} {}: {}", name, v, e.to_string())))
+        .transpose()
+}
+
+/// Reads the project details given as flags.
+fn preset(matches: &ArgMatches) -> Result<Preset, String> {
+    let license = matches.value_of("license").map(String::from);
+
+    if let Some(l) = &license {
+        Project::parse_license(l).map_err(|e| format!("--license {}: {}", l, e.reason.unwrap_or_default()))?;
+    }
+
+    Ok(Preset {
+        namespace: matches.value_of("namespace").map(String::from),
+        description: matches.value_of("description").map(String::from),
+        authors: matches.values_of("author").map(|a| a.map(String::from).collect()),
+        version: parse(matches, "version")?,
+        license,
+        homepage: parse(matches, "homepage")?,
+        repository: parse(matches, "repository")?
+    })
+}
+
+/// Plans, asks for and writes the project, checking for existing files before anything is asked.
+///
+/// Nothing is asked without a terminal to ask on; the preset is filled in with the user's defaults instead.
+fn create(matches: &ArgMatches, name: String, preset: Preset, config: &UserConfig, source: &Source) -> Result<(), Error> {
+    let defaults = config.defaults(&name);
+    let force = matches.is_present("force");
+
+    // The preset filled in with the user's defaults, for when nothing is asked
+    let filled = Preset {
+        authors: preset.authors.clone().or_else(|| defaults.authors.clone()),
+        license: preset.license.clone().or_else(|| defaults.license.clone()),
+        repository: preset.repository.clone().or_else(|| defaults.repository.clone()),
+        ..preset.clone()
+    };
+
+    // Nothing is asked or written if a file would be overwritten
+    let planned = Plan::new(source, &Project::from_preset(name.clone(), &filled), config)?;
+    planned.review(force)?;
+
+    // The project data object
+    // Take input if default argument not supplied and there's a terminal, otherwise fall back to the user's defaults
+    let project = if matches.is_present("default") || !stdin().is_terminal() {
+        Project::from_preset(name, &filled)
+    } else {
+        input_project(name, &preset, &defaults).map_err(|e| if cancelled(&e) {
+            Error::new("Cancelled.", None)
+        } else {
+            Error::new("Failed to read input.", Some(e.to_string()))
+        })?
+    };
+
+    let plan = Plan::new(source, &project, config)?;
+
+    // The answers can change which files are created, such as the license files
+    if plan.names() != planned.names() {
+        plan.review(force)?;
+    }
+
+    check_licenses(&project, &plan.unknown);
+
+    let mut changes = Changes::default();
+
+    if let Err(e) = plan.write(&mut changes, config.git()) {
+        let count = changes.rollback();
+        return Err(Error::new("Failed to create project.", Some(format!("{} Rolled back {} change(s).", e, count))));
+    }
+
+    if let Source::Style(style) = source {
+        if let Some(entry) = style.entry(&project) {
+            println!("\nEntry script: {}", entry);
+        }
+    }
+
+    Ok(())
+}
 
 pub fn new(matches: &ArgMatches) {
     // Name of the project
     let name: String = matches.value_of("NAME").unwrap().to_string();
+    // Project details given as flags
+    let preset = match preset(matches) {
+        Ok(preset) => preset,
+        Err(e) => {
+            err("Invalid argument.", Some(e));
+            exit(1);
+        }
+    };
+    // The user's defaults, already reported if invalid
+    let config = read_config().unwrap_or_default();
     // Style argument
-    let style = matches.value_of("style").unwrap_or("default");
-    // Forced to discontinue if unable to read style
-    match read_style(style) {
-        Ok(dirs) => {
-            // The project data object
-            // Take input if default argument not supplied
-            let project = if matches.is_present("default") {
-                Ok(Project::from_name(name))
-            } else {
-                Project::from_input(name)
-            };
-            
-            if let Ok(project) = project {
-                // Create style directories
-                for dir in &dirs {
-                    if dir != "." {
-                        create_dir(dir.as_str(), true);
-                    }
-                }
-                // Main dsc file
-                create(
-                    format!("{}/{}.dsc", dirs.last().unwrap(), &project.name).as_str(), 
-                    format!("{}:{}", &project.name, MAIN), true);
-                // README & LICENSE
-                create("README.md", project.readme_header(), true);
-                create("LICENSE", project.license_text(), true);
-                // Create dzp directory
-                create_dir(".dzp", true);
-                create(".dzp/project", project.yaml(), true);
-                // Initialize git repository if possible
-                if let Err(e) = Repository::init(current_dir().unwrap()) {
-                    err("Failed to initialize git repository.", Some(e.to_string()))
-                }
-            }
+    let style = matches.value_of("style").or(config.style.as_deref()).unwrap_or("default");
+    // Forced to discontinue if unable to read style or fetch template
+    let source = match matches.value_of("template") {
+        Some(template) => fetch(template).map(Source::Template),
+        None => read_style(style).map(Source::Style)
+    };
+
+    let source = match source {
+        Ok(source) => source,
+        Err(e) => {
+            e.log();
+            exit(1);
         }
-        Err(e) => err("Invalid style.", Some(e.to_string()))
+    };
+
+    let result = create(matches, name, preset, &config, &source);
+
+    // The fetched template is only needed while creating the project
+    if let Source::Template(dir) = &source {
+        clean(dir);
+    }
+
+    if let Err(e) = result {
+        e.log();
+        exit(1);
     }
-}
\ No newline at end of file
+}
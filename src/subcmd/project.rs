@@ -1,12 +1,96 @@
+use std::fs::{read_to_string, write};
+
 use clap::ArgMatches;
+use serde_yaml::{from_str, to_value, Value};
+
+use dzp::Error;
+use dzp::io::{fs::{get_project, project_path}, yaml::set_key};
+use dzp::structs::project::{Preset, Project};
+
+use super::input::{cancelled, input_project};
+
+/// The fields `dzp project edit` asks for.
+const PROMPTED: [&str; 6] = ["description", "authors", "version", "license", "homepage", "repository"];
+
+/// Writes changes to the project file, keeping its other keys and comments.
+pub fn update(path: Option<&str>, changes: &[(&str, Value)]) -> Result<(), Error> {
+    let file = project_path(path);
+    let text = read_to_string(&file).map_err(|e| Error::new("Failed to read project file.", Some(e.to_string())))?;
+    let text = changes.iter().fold(text, |text, (key, value)| set_key(&text, key, value));
+
+    from_str::<Project>(&text).map_err(|e| Error::new("Invalid project file.", Some(e.to_string())))?;
+    write(&file, text).map_err(|e| Error::new("Failed to write project file.", Some(e.to_string())))
+}
+
+fn set(path: Option<&str>, matches: &ArgMatches) -> Result<(), Error> {
+    let field = matches.value_of("FIELD").unwrap();
+    let value = matches.value_of("VALUE").unwrap_or_default();
+
+    let yaml = Project::field_value(field, value)?;
+    update(path, &[(field, yaml)])?;
+
+    if value.is_empty() {
+        println!("Unset {}.", field);
+    } else {
+        println!("Set {} to {}.", field, value);
+    }
+
+    Ok(())
+}
 
-use crate::io::fs::get_project;
+fn add_author(path: Option<&str>, matches: &ArgMatches) -> Result<(), Error> {
+    let name = matches.value_of("NAME").unwrap().trim();
+    let mut project = get_project(path)?;
+
+    if project.authors.iter().any(|a| a == name) {
+        return Err(Error::new("Author already exists.", Some(name.to_owned())));
+    }
+
+    project.authors.push(name.to_owned());
+    update(path, &[("authors", to_value(&project.authors).unwrap())])?;
+    println!("Added author '{}'.", name);
+    Ok(())
+}
+
+fn edit(path: Option<&str>) -> Result<(), Error> {
+    let project = get_project(path)?;
+
+    let edited = match input_project(project.name.clone(), &Preset::default(), &project.preset()) {
+        Ok(edited) => edited,
+        Err(e) if cancelled(&e) => return Ok(()),
+        Err(e) => return Err(Error::new("Failed to read input.", Some(e.to_string())))
+    };
+
+    let (old, new) = (to_value(&project).unwrap(), to_value(&edited).unwrap());
+    let changes = PROMPTED.iter()
+        .filter(|key| old[**key] != new[**key])
+        .map(|key| (*key, new[*key].clone()))
+        .collect::<Vec<(&str, Value)>>();
+
+    if changes.is_empty() {
+        println!("Nothing changed.");
+        return Ok(());
+    }
+
+    update(path, &changes)?;
+    println!("Updated {}.", changes.iter().map(|(k, _)| *k).collect::<Vec<&str>>().join(", "));
+    Ok(())
+}
 
 pub fn project(matches: &ArgMatches) {
     let path = matches.value_of("path");
 
-    if let Ok(project) = get_project(path) {
-        println!();
-        project.print();
+    let result = match matches.subcommand() {
+        ("set", Some(sub)) => set(path, sub),
+        ("add-author", Some(sub)) => add_author(path, sub),
+        ("edit", Some(_)) => edit(path),
+        _ => get_project(path).map(|project| {
+            println!();
+            project.print();
+        })
+    };
+
+    if let Err(e) = result {
+        e.log();
     }
-}
\ No newline at end of file
+}
@@ -1,7 +1,93 @@
-use crate::io::fs::{create, get_project};
+use std::fs::read_to_string;
+use std::process::exit;
 
-pub fn readme() {
-    if let Ok(project) = get_project(None) {
-        create("README.md", project.readme(), true);
+use clap::ArgMatches;
+
+use dzp::config::{templates::read_readme, user::read_config};
+use dzp::io::{fs::{create, get_project}, log::err};
+use dzp::readme::{regions::{all, contains_any, find, update}, template::render};
+use dzp::structs::project::Detail;
+
+use super::{creating, scripts};
+
+const README: &str = "README.md";
+
+pub fn readme(matches: &ArgMatches) {
+    let check = matches.is_present("check");
+
+    let project = match get_project(None) {
+        Ok(project) => project,
+        Err(e) => return e.log()
+    };
+
+    // The README as it is, unless it's regenerated
+    let current = read_to_string(README).ok().filter(|_| !matches.is_present("force"));
+
+    // Without --detail, the scripts are listed the way they already are
+    let detail = match matches.value_of("detail") {
+        Some(detail) => detail.parse().unwrap_or(Detail::Names),
+        None => current.as_deref()
+            .and_then(|c| find(c, "scripts"))
+            .and_then(|r| r.option("detail").and_then(|d| d.parse().ok()))
+            .unwrap_or(Detail::Names)
+    };
+
+    let scripts = scripts(true);
+    let config = read_config().unwrap_or_default();
+
+    // Use the README template if there is one
+    let generated = match read_readme(".") {
+        Some(template) => {
+            match render(&template, &project, &scripts, detail, &config) {
+                Ok(text) => text,
+                Err(e) => {
+                    e.log();
+                    exit(1);
+                }
+            }
+        }
+        None => project.readme(&scripts, detail, &config)
+    };
+
+    let current = match current {
+        Some(current) => current,
+        None => {
+            if check {
+                err("README.md does not exist.", None);
+                exit(1);
+            }
+
+            creating(README);
+
+            if let Err(e) = create(README, generated) {
+                e.log();
+            }
+
+            return;
+        }
+    };
+
+    let regions = all(&generated);
+
+    if !contains_any(&current, &regions) {
+        err("README.md has no dzp-managed sections.", Some("Use --force to regenerate it.".to_owned()));
+        exit(1);
+    }
+
+    let (updated, stale) = update(&current, &regions);
+
+    if check {
+        if stale.is_empty() {
+            println!("README.md is up to date.");
+        } else {
+            err("README.md is out of date.", Some(stale.join(", ")));
+            exit(1);
+        }
+    } else {
+        creating(README);
+
+        if let Err(e) = create(README, updated) {
+            e.log();
+        }
     }
-}
\ No newline at end of file
+}
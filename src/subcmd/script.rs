@@ -1,10 +1,75 @@
+use std::collections::BTreeSet;
+
 use clap::ArgMatches;
 
-use crate::io::log::err; 
-use crate::cache::scripts::get_scripts;
+use dzp::analysis::body::{defined, references, used};
+use dzp::cache::scripts::{find_scripts_raw, script_source, ScriptContents, ScriptFile};
+use dzp::io::{highlight::highlight, log::err};
+use dzp::structs::script::Script;
+
+use super::scripts;
+
+fn print_list(title: &str, items: &[String]) {
+    println!("\n{}:", title);
+
+    if items.is_empty() {
+        println!("(none)");
+    }
+
+    for item in items {
+        println!("- {}", item);
+    }
+}
+
+fn source(name: &str, script: &Script) {
+    match script_source(&script.path, name) {
+        Some(text) => println!("\n{}", highlight(&text)),
+        None => err("Failed to read the script's source.", Some(script.path.clone()))
+    }
+}
+
+fn refs(name: &str, files: &[(String, ScriptFile)]) {
+    let names = files.iter()
+        .flat_map(|(_, file)| file.keys().cloned())
+        .filter(|n| n != name)
+        .collect::<BTreeSet<String>>();
+
+    let calls = files.iter()
+        .flat_map(|(_, file)| file.get(name))
+        .flat_map(|contents| references(contents, &names))
+        .collect::<Vec<String>>();
+
+    let target = std::iter::once(name.to_owned()).collect::<BTreeSet<String>>();
+    let callers = files.iter()
+        .flat_map(|(_, file)| file.iter())
+        .filter(|(n, contents)| *n != name && !references(contents, &target).is_empty())
+        .map(|(n, _)| n.clone())
+        .collect::<Vec<String>>();
+
+    print_list("Calls", &calls);
+    print_list("Called by", &callers);
+}
+
+fn defs(contents: &ScriptContents) {
+    let set = defined(contents);
+
+    let list = set.iter()
+        .map(|(d, source)| format!("{} ({})", d, source))
+        .collect::<Vec<String>>();
+
+    let undefined = used(contents).into_iter()
+        .filter(|d| !set.contains_key(d))
+        .collect::<Vec<String>>();
+
+    print_list("Definitions", &list);
+
+    if !undefined.is_empty() {
+        print_list("Used but never defined", &undefined);
+    }
+}
 
 pub fn script(matches: &ArgMatches) {
-    let scripts = get_scripts(matches.is_present("force"));
+    let scripts = scripts(matches.is_present("force"));
 
     match matches.value_of("NAME") {
         Some(name) => {
@@ -12,14 +77,31 @@ pub fn script(matches: &ArgMatches) {
                 Some(s) => {
                     println!();
                     s.print(name);
+
+                    if matches.is_present("source") {
+                        source(name, s);
+                    }
+
+                    if matches.is_present("refs") || matches.is_present("defs") {
+                        let files = find_scripts_raw(".");
+
+                        if matches.is_present("refs") {
+                            refs(name, &files);
+                        }
+
+                        if matches.is_present("defs") {
+                            if let Some(contents) = files.iter().find_map(|(_, file)| file.get(name)) {
+                                defs(contents);
+                            }
+                        }
+                    }
                 }
                 None => err("Failed to find script.", None)
             }
         },
         None => {
             let keys = scripts.keys()
-                .clone()
-                .map(|s| String::from(s))
+                .cloned()
                 .collect::<Vec<String>>()
                 .join(", ");
 
@@ -0,0 +1,35 @@
+use ansi_term::Color::Green;
+use clap::ArgMatches;
+
+use dzp::analysis::search::{search as search_scripts, Filter};
+use dzp::io::log::{err, paint};
+
+use super::scripts;
+
+pub fn search(matches: &ArgMatches) {
+    // Results always reflect the scripts as they are now
+    let scripts = scripts(true);
+    let query = matches.values_of("QUERY").unwrap_or_default().collect::<Vec<&str>>().join(" ");
+
+    let filter = Filter {
+        script_type: matches.value_of("type").map(String::from),
+        path: matches.value_of("path").map(String::from)
+    };
+
+    let results = search_scripts(&scripts, &query, &filter);
+
+    if results.is_empty() {
+        return err("No scripts found.", None);
+    }
+
+    for result in results {
+        let script = &scripts[&result.name];
+        let description = script.doc.as_ref()
+            .and_then(|d| d.description.clone())
+            .map(|d| format!(" - {}", d))
+            .unwrap_or_default();
+
+        println!("{} ({}){}", paint(Green, &result.name), script.script_type, description);
+        println!("  {} · matched {}", script.path, result.fields.join(", "));
+    }
+}
\ No newline at end of file
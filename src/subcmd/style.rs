@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+
+use ansi_term::Color::Green;
+use clap::ArgMatches;
+
+use dzp::Error;
+use dzp::config::styles::{add_style, create_style, read_style, remove_style, reset_styles, style_location, style_names, BUILTIN};
+use dzp::io::log::{err, paint, warn};
+
+fn location(name: &str) -> String {
+    match style_location(name) {
+        Some(path) => path.display().to_string(),
+        None => "built-in".to_owned()
+    }
+}
+
+fn print_list(title: &str, items: &[String]) {
+    if !items.is_empty() {
+        println!("\n{}:", title);
+
+        for item in items {
+            println!("- {}", item);
+        }
+    }
+}
+
+fn list() {
+    for name in style_names() {
+        let builtin = BUILTIN.iter().any(|(n, _)| *n == name);
+
+        let info = match read_style(&name) {
+            Ok(style) => style.description.map(|d| format!(" - {}", d)).unwrap_or_default(),
+            Err(e) => format!(" (invalid: {})", e.reason.unwrap_or(e.text))
+        };
+
+        println!("{}{}{}", paint(Green, &name), info, if builtin { " [built-in]" } else { "" });
+    }
+}
+
+fn show(name: &str) {
+    let style = match read_style(name) {
+        Ok(style) => style,
+        Err(e) => return err("Failed to read style.", Some(e.to_string()))
+    };
+
+    println!("Name: {}", name);
+    println!("Location: {}", location(name));
+
+    if let Some(description) = &style.description {
+        println!("Description: {}", description);
+    }
+
+    if let Some(entry) = &style.entry {
+        println!("Entry: {}", entry);
+    }
+
+    print_list("Directories", &style.dirs);
+    print_list("Files", &style.files.keys().cloned().collect::<Vec<String>>());
+}
+
+fn report(action: (&str, &str), name: &str, result: Result<PathBuf, Error>) {
+    match result {
+        Ok(path) => println!("{} style '{}' ({}).", action.1, name, path.display()),
+        Err(e) => err(&format!("Failed to {} style.", action.0), Some(e.to_string()))
+    }
+}
+
+fn reset(matches: &ArgMatches) {
+    match reset_styles(matches.value_of("NAME")) {
+        Ok(styles) => {
+            for style in styles {
+                println!("Reset style '{}'.", style);
+            }
+        }
+        Err(e) => err("Failed to reset styles.", Some(e.to_string()))
+    }
+}
+
+pub fn style(matches: &ArgMatches) {
+    match matches.subcommand() {
+        ("list", Some(_)) => list(),
+        ("show", Some(sub)) => show(sub.value_of("NAME").unwrap()),
+        ("add", Some(sub)) => {
+            let name = sub.value_of("NAME").unwrap();
+            report(("add", "Added"), name, add_style(name, sub.value_of("SOURCE").unwrap()));
+        }
+        ("remove", Some(sub)) => {
+            let name = sub.value_of("NAME").unwrap();
+            report(("remove", "Removed"), name, remove_style(name));
+        }
+        ("create", Some(sub)) => {
+            let name = sub.value_of("NAME").unwrap();
+
+            let (result, skipped) = match create_style(name, sub.value_of("from").unwrap()) {
+                Ok((path, skipped)) => (Ok(path), skipped),
+                Err(e) => (Err(e), Vec::new())
+            };
+
+            report(("create", "Created"), name, result);
+
+            if !skipped.is_empty() {
+                warn("Left out files that aren't text, since styles can only hold text; use the project with `dzp new --template` to keep them.", Some(skipped.join(", ")));
+            }
+        }
+        ("reset", Some(sub)) => reset(sub),
+        _ => ()
+    }
+}
\ No newline at end of file